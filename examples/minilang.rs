@@ -9,7 +9,8 @@ use std::{
 
 use errgonomic::{
     combinators::{
-        any, between, decimal, eoi, is, maybe, panic_recover, whitespace, whitespace_wrapped as ww,
+        any, between, eoi, integer, is, maybe, panic_recover, seq, terminated, whitespace,
+        whitespace_wrapped as ww,
     },
     parser::{
         errors::{CustomError, Result},
@@ -97,11 +98,8 @@ impl fmt::Display for Operator {
 }
 
 fn number(state: State<&str, ParseError>) -> Result<&str, Expression, ParseError> {
-    ww(decimal)
-        // NOTE: See `examples/hex.rs` for why the `unwrap` is safe
-        .map(|n: Input<&str>| {
-            Expression::Number(n.as_inner().parse::<i32>().expect("a valid number"))
-        })
+    ww(integer::<i32, _, _, ()>())
+        .map(Expression::Number)
         .process(state)
 }
 
@@ -122,9 +120,9 @@ fn operator(state: State<&str, ParseError>) -> Result<&str, Operator, ParseError
 }
 
 fn operation(state: State<&str, ParseError>) -> Result<&str, Expression, ParseError> {
-    let (state, (((op, left), _), right)) = between(
+    let (state, (op, left, right)) = between(
         ww(is("(")),
-        operator.then(ww(value)).then(maybe(whitespace)).then(value),
+        seq((operator, terminated(ww(value), maybe(whitespace)), value)),
         ww(is(")")),
     )
     .process(state)?;
@@ -1,6 +1,6 @@
 //! This demonstrates how to parse recursive lists using `errgonomic`.
 //! It also shows how to use the parser combinators to add additional positional information to
-//! your structured output, via `Input`.
+//! your structured output, via `Spanned` (see `Parser::with_span`).
 
 use std::{
     fmt,
@@ -8,30 +8,20 @@ use std::{
 };
 
 use errgonomic::{
-    combinators::{any, between, decimal, is, separated, whitespace_wrapped as ww},
-    parser::{errors::Result, input::Input, state::State, Parser},
+    combinators::{any, between, integer, is, separated, whitespace_wrapped as ww},
+    parser::{errors::Result, input::Spanned, state::State, Parser},
 };
 
-#[derive(Debug)]
-pub struct Value<'a> {
-    #[allow(dead_code)]
-    s: Input<&'a str>,
-    v: ValueInner<'a>,
-}
-
-impl<'a> Value<'a> {
-    pub fn new(v: ValueInner<'a>, s: Input<&'a str>) -> Self {
-        Self { v, s }
-    }
-}
+/// A value, along with where it is in the input.
+pub type Value = Spanned<ValueInner>;
 
 #[derive(Debug)]
-pub enum ValueInner<'a> {
+pub enum ValueInner {
     Number(i32),
-    List(Vec<Value<'a>>),
+    List(Vec<Value>),
 }
 
-impl fmt::Display for ValueInner<'_> {
+impl fmt::Display for ValueInner {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Number(n) => write!(f, "{}", n),
@@ -49,31 +39,19 @@ impl fmt::Display for ValueInner<'_> {
     }
 }
 
-impl fmt::Display for Value<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.v)
-    }
-}
-
 fn number(state: State<&str>) -> Result<&str, Value> {
-    ww(decimal)
-        // NOTE: See `examples/hex.rs` for why the `unwrap` is safe
-        .map(|n: Input<&str>| {
-            Value::new(ValueInner::Number(n.as_inner().parse::<i32>().unwrap()), n)
-        })
-        .process(state)
+    ww(integer::<i32, _, _, ()>()
+        .map(ValueInner::Number)
+        .with_span())
+    .process(state)
 }
 
 fn list(state: State<&str>) -> Result<&str, Value> {
-    ww(between(
-        is("["),
-        separated(value, ww(is(",")), true),
-        is("]"),
-    ))
-    .map_with_state(|state, vs| {
-        let input = state.as_input().fork();
-        (state, Value::new(ValueInner::List(vs), input))
-    })
+    ww(
+        between(is("["), separated(value, ww(is(",")), true), is("]"))
+            .map(ValueInner::List)
+            .with_span(),
+    )
     .process(state)
 }
 
@@ -96,7 +74,7 @@ pub fn main() {
         stdin().read_line(&mut s).unwrap();
 
         match parser.parse(s.trim()) {
-            Ok(x) => println!("Parsed: {}", x),
+            Ok(x) => println!("Parsed: {} (at {:?})", x, x.span),
             Err(err) => eprintln!("Error: {:?}", err),
         }
 
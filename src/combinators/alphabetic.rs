@@ -1,13 +1,15 @@
-use super::many_n;
+use super::glyph::{glyph, glyphs};
 use crate::parser::{
-    errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
+    errors::{CustomError, ExpectedError, Result},
     input::{Input, Underlying},
     state::State,
-    Parser,
 };
 
 /// Parses an alphabetic character until it stops. If there is no alphabetic character, returns an error.
-/// TODO: Unicode support
+///
+/// NOTE: When the `unicode` feature is enabled, this will parse any character with the Unicode
+/// `Alphabetic` property. Otherwise, only ASCII letters are parsed. For exactly the Unicode
+/// letters (`L*`), use `unicode_letter` instead.
 ///
 ///```
 /// # use errgonomic::combinators::alphabetic_char;
@@ -18,25 +20,17 @@ use crate::parser::{
 /// assert_eq!(parsed, "a");
 /// assert_eq!(state.as_input().as_inner(), "bc123");
 ///```
-pub fn alphabetic_char<I: Underlying, E: CustomError>(
-    state: State<I, E>,
-) -> Result<I, Input<I>, E> {
-    let input = state.as_input().fork();
-    match input.peek() {
-        Some(c) if c.is_ascii_alphabetic() => {
-            let num = input.take(1);
-            Ok((state.with_input(input.skip(1)), num))
-        }
-        _ => Err(state.with_error(Error::new(
-            ErrorKind::expected(ExpectedError::Digit(10)),
-            input.take(1),
-        ))),
-    }
+pub fn alphabetic_char<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyph(state, is_alphabetic, ExpectedError::Alpha)
 }
 
 /// Parses a string of alphabetic characters until it stops. If there is no alphabetic character,
 /// returns an error.
 ///
+/// NOTE: See `alphabetic_char` for what counts as alphabetic.
+///
 ///```
 /// # use errgonomic::combinators::alphabetic;
 /// # use errgonomic::parser::Parser;
@@ -46,18 +40,17 @@ pub fn alphabetic_char<I: Underlying, E: CustomError>(
 /// assert_eq!(parsed, "abc");
 /// assert_eq!(state.as_input().as_inner(), "123");
 ///```
-pub fn alphabetic<I: Underlying, E: CustomError>(state: State<I, E>) -> Result<I, Input<I>, E> {
-    many_n(1, alphabetic_char)
-        .map(|xs| {
-            xs.into_iter()
-                .reduce(|acc, x| acc.join(&x))
-                .expect("to have parsed at least one character!")
-        })
-        .process(state)
+pub fn alphabetic<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyphs(state, is_alphabetic, ExpectedError::Alpha)
 }
 
 /// Parses an alphanumeric character until it stops. If there is no alphanumeric character, returns an error.
-/// TODO: Unicode support
+///
+/// NOTE: When the `unicode` feature is enabled, this will parse any character with the Unicode
+/// `Alphabetic` property or in the `N*` (numeric) general categories. Otherwise, only ASCII
+/// letters and digits are parsed.
 ///
 ///```
 /// # use errgonomic::combinators::alphanumeric_char;
@@ -68,25 +61,17 @@ pub fn alphabetic<I: Underlying, E: CustomError>(state: State<I, E>) -> Result<I
 /// assert_eq!(parsed, "a");
 /// assert_eq!(state.as_input().as_inner(), "bc123");
 ///```
-pub fn alphanumeric_char<I: Underlying, E: CustomError>(
-    state: State<I, E>,
-) -> Result<I, Input<I>, E> {
-    let input = state.as_input().fork();
-    match input.peek() {
-        Some(c) if c.is_ascii_alphanumeric() => {
-            let num = input.take(1);
-            Ok((state.with_input(input.skip(1)), num))
-        }
-        _ => Err(state.with_error(Error::new(
-            ErrorKind::expected(ExpectedError::Digit(10)),
-            input.take(1),
-        ))),
-    }
+pub fn alphanumeric_char<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyph(state, is_alphanumeric, ExpectedError::AlphaNum)
 }
 
 /// Parses a string of alphanumeric characters until it stops. If there is no alphabetic character,
 /// returns an error.
 ///
+/// NOTE: See `alphanumeric_char` for what counts as alphanumeric.
+///
 ///```
 /// # use errgonomic::combinators::alphanumeric;
 /// # use errgonomic::parser::Parser;
@@ -96,12 +81,116 @@ pub fn alphanumeric_char<I: Underlying, E: CustomError>(
 /// assert_eq!(parsed, "abc123");
 /// assert_eq!(state.as_input().as_inner(), "");
 ///```
-pub fn alphanumeric<I: Underlying, E: CustomError>(state: State<I, E>) -> Result<I, Input<I>, E> {
-    many_n(1, alphanumeric_char)
-        .map(|xs| {
-            xs.into_iter()
-                .reduce(|acc, x| acc.join(&x))
-                .expect("to have parsed at least one character!")
-        })
-        .process(state)
+pub fn alphanumeric<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyphs(state, is_alphanumeric, ExpectedError::AlphaNum)
+}
+
+/// Whether `c` is alphabetic. See `alphabetic_char`.
+#[inline]
+fn is_alphabetic(c: char) -> bool {
+    #[cfg(not(feature = "unicode"))]
+    {
+        c.is_ascii_alphabetic()
+    }
+    #[cfg(feature = "unicode")]
+    {
+        c.is_alphabetic()
+    }
+}
+
+/// Whether `c` is alphanumeric. See `alphanumeric_char`.
+#[inline]
+fn is_alphanumeric(c: char) -> bool {
+    #[cfg(not(feature = "unicode"))]
+    {
+        c.is_ascii_alphanumeric()
+    }
+    #[cfg(feature = "unicode")]
+    {
+        c.is_alphanumeric()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{
+        errors::{Error, ErrorKind},
+        Parser,
+    };
+
+    #[test]
+    fn can_parse_alphabetic() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            alphabetic.process("abc123".into()).unwrap();
+        assert_eq!(parsed, "abc");
+        assert_eq!(state.as_input(), &"123");
+        assert!(!state.is_err());
+
+        let state: State<&str> = alphabetic.process("123".into()).unwrap_err();
+        assert_eq!(state.errors().len(), 1);
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Alpha),
+                Input::new_with_span("123", 0..1)
+            )
+        );
+    }
+
+    #[test]
+    fn can_parse_alphanumeric() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            alphanumeric.process("abc123-".into()).unwrap();
+        assert_eq!(parsed, "abc123");
+        assert_eq!(state.as_input(), &"-");
+        assert!(!state.is_err());
+
+        let state: State<&str> = alphanumeric_char.process("-".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::AlphaNum),
+                Input::new_with_span("-", 0..1)
+            )
+        );
+    }
+
+    #[cfg(not(feature = "unicode"))]
+    #[test]
+    fn only_parses_ascii_alphabetic() {
+        let state: State<&str> = alphabetic.process("éa".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Alpha),
+                Input::new_with_span("éa", 0..1)
+            )
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn can_parse_unicode_alphabetic() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            alphabetic.process("héllöЖ中1".into()).unwrap();
+        assert_eq!(parsed, "héllöЖ中");
+        assert_eq!(state.as_input(), &"1");
+
+        let (state, parsed): (State<&str>, Input<&str>) =
+            alphanumeric.process("é٣²!".into()).unwrap();
+        assert_eq!(parsed, "é٣²");
+        assert_eq!(state.as_input(), &"!");
+
+        let state: State<&str> = alphabetic_char.process("😊".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Alpha),
+                Input::new_with_span("😊", 0..4)
+            )
+        );
+    }
 }
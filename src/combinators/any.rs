@@ -19,7 +19,9 @@ use eval_macro::eval;
 ///```
 #[inline]
 #[allow(private_bounds)]
-pub fn any<I: Underlying, O, E: CustomError, L: List<I, O, E>>(ps: L) -> impl Parser<I, O, E> {
+pub fn any<I: Underlying, O, E: CustomError, C, L: List<I, O, E, C>>(
+    ps: L,
+) -> impl Parser<I, O, E, C> {
     move |state| ps.any(state)
 }
 
@@ -28,7 +30,9 @@ pub fn any<I: Underlying, O, E: CustomError, L: List<I, O, E>>(ps: L) -> impl Pa
 /// definately say that we are supposed to be on this branch, *and* an error occured, then when we
 /// go back up the branch we only return those errors.
 #[inline]
-pub fn commit<I: Underlying, O, E: CustomError, P: Parser<I, O, E>>(p: P) -> impl Parser<I, O, E> {
+pub fn commit<I: Underlying, O, E: CustomError, C, P: Parser<I, O, E, C>>(
+    p: P,
+) -> impl Parser<I, O, E, C> {
     move |state| match p.process(state) {
         Ok(x) => Ok(x),
         Err(e) => Err(e.commit()),
@@ -38,12 +42,12 @@ pub fn commit<I: Underlying, O, E: CustomError, P: Parser<I, O, E>>(p: P) -> imp
 /* TRAIT IMPLEMENTATIONS NEEDED FOR ANY */
 /* These are annoying and long, you can ignore*/
 
-trait List<I: Underlying, O, E: CustomError> {
-    fn any(&self, state: State<I, E>) -> Result<I, O, E>;
+trait List<I: Underlying, O, E: CustomError, C> {
+    fn any(&self, state: State<I, E, C>) -> Result<I, O, E, C>;
 }
 
-impl<I: Underlying, O, E: CustomError, P: Parser<I, O, E>> List<I, O, E> for &[P] {
-    fn any(&self, state: State<I, E>) -> Result<I, O, E> {
+impl<I: Underlying, O, E: CustomError, C, P: Parser<I, O, E, C>> List<I, O, E, C> for &[P] {
+    fn any(&self, state: State<I, E, C>) -> Result<I, O, E, C> {
         let mut errs = vec![];
 
         for parser in self.iter() {
@@ -64,8 +68,8 @@ impl<I: Underlying, O, E: CustomError, P: Parser<I, O, E>> List<I, O, E> for &[P
     }
 }
 
-impl<I: Underlying, O, E: CustomError, P: Parser<I, O, E>> List<I, O, E> for &Vec<P> {
-    fn any(&self, state: State<I, E>) -> Result<I, O, E> {
+impl<I: Underlying, O, E: CustomError, C, P: Parser<I, O, E, C>> List<I, O, E, C> for &Vec<P> {
+    fn any(&self, state: State<I, E, C>) -> Result<I, O, E, C> {
         assert!(!self.is_empty(), "There should be at least 1 parser!");
         let mut errs = vec![];
 
@@ -102,7 +106,7 @@ eval! {
         let parser_tuple = format!("({parser_generics},)");
         let parser_defs = (1..=n)
             .into_iter()
-            .map(|i| format!("P{i}: Parser<I, O, E>"))
+            .map(|i| format!("P{i}: Parser<I, O, E, C>"))
             .collect::<Vec<_>>()
             .join(",\n");
         let processing = (0..n)
@@ -116,14 +120,14 @@ eval! {
             .join("\n\n");
 
         output! {
-            impl<I, O, E, {{parser_generics}}> List<I, O, E> for {{parser_tuple}}
+            impl<I, O, E, C, {{parser_generics}}> List<I, O, E, C> for {{parser_tuple}}
             where
                 I: Underlying,
                 E: CustomError,
                 {{parser_defs}}
             {
                 #[inline]
-                fn any(&self, state: State<I, E>) -> Result<I, O, E> {
+                fn any(&self, state: State<I, E, C>) -> Result<I, O, E, C> {
                     let mut errs: Vec<Error<I, E>> = vec![];
 
                     {{processing}}
@@ -161,7 +165,7 @@ mod tests {
         assert_eq!(state.as_input(), &"123");
         assert!(!state.is_err());
 
-        let parsed: Input<&str> = any((id::<_, DummyError>, is("test")))
+        let parsed: Input<&str> = any((id::<_, DummyError, ()>, is("test")))
             .parse("test123")
             .unwrap();
         assert_eq!(parsed, "test123");
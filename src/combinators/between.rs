@@ -9,18 +9,18 @@ use crate::parser::{errors::CustomError, input::Underlying, Parser};
 /// # use errgonomic::parser::input::Input;
 /// # use errgonomic::parser::state::State;
 /// # use errgonomic::parser::errors::DummyError;
-/// let result = between(is::<_, DummyError>("("), decimal, is(")")).parse("(123)").unwrap();
+/// let result = between(is::<_, DummyError, ()>("("), decimal, is(")")).parse("(123)").unwrap();
 /// assert_eq!(result, "123");
 ///```
-pub fn between<I: Underlying, O1, O2, O3, E: CustomError, P1, P2, P3>(
+pub fn between<I: Underlying, O1, O2, O3, E: CustomError, C, P1, P2, P3>(
     open: P1,
     parser: P2,
     close: P3,
-) -> impl Parser<I, O2, E>
+) -> impl Parser<I, O2, E, C>
 where
-    P1: Parser<I, O1, E>,
-    P2: Parser<I, O2, E>,
-    P3: Parser<I, O3, E>,
+    P1: Parser<I, O1, E, C>,
+    P2: Parser<I, O2, E, C>,
+    P3: Parser<I, O3, E, C>,
 {
     open.then(parser).then(close).map(|((_, o), _)| o)
 }
@@ -0,0 +1,189 @@
+use super::glyph::glyph;
+use crate::parser::{
+    errors::{CustomError, ExpectedError, Result},
+    input::{Input, Underlying},
+    state::State,
+    Parser,
+};
+use core::ops::RangeInclusive;
+
+pub use crate::parser::class::CharClass;
+
+/// Parses a single character that matches `f`. If it doesn't match, returns an error.
+///
+/// NOTE: Without the `unicode` feature, each byte is passed to `f` as a `char` on its own, so
+/// non-ASCII characters never match as a whole. With it, whole characters are passed.
+///
+/// NOTE: As `f` can't describe itself, the error only says that the character didn't match. Prefer
+/// a `CharClass` (`one_of`, `none_of`, `char_range`) where possible for better error messages.
+///
+/// ```
+/// # use errgonomic::combinators::satisfy;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) =
+///     satisfy(|c| c.is_ascii_uppercase()).process("Ab".into()).unwrap();
+/// assert_eq!(parsed, "A");
+/// assert_eq!(state.as_input().as_inner(), "b");
+/// ```
+pub fn satisfy<I: Underlying, E: CustomError, C, F: Fn(char) -> bool>(
+    f: F,
+) -> impl Parser<I, Input<I>, E, C> {
+    move |state: State<I, E, C>| glyph(state, &f, ExpectedError::Satisfy)
+}
+
+/// Parses a single character that is any of the characters in `chars`. See `CharClass`.
+///
+/// ```
+/// # use errgonomic::combinators::one_of;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = one_of("+-*/").process("*2".into()).unwrap();
+/// assert_eq!(parsed, "*");
+/// assert_eq!(state.as_input().as_inner(), "2");
+/// ```
+pub fn one_of(chars: &str) -> CharClass {
+    CharClass::from_ranges(chars.chars().map(|c| c..=c))
+}
+
+/// Parses a single character that is *not* any of the characters in `chars`. See `CharClass`.
+///
+/// NOTE: Like `not`, this does not match the end of input.
+///
+/// ```
+/// # use errgonomic::combinators::none_of;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = none_of("\"\\").process("a\"".into()).unwrap();
+/// assert_eq!(parsed, "a");
+/// assert_eq!(state.as_input().as_inner(), "\"");
+/// ```
+pub fn none_of(chars: &str) -> CharClass {
+    one_of(chars).negate()
+}
+
+/// Parses a single character in `range`. See `CharClass`.
+///
+/// ```
+/// # use errgonomic::combinators::char_range;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = char_range('a'..='f')
+///     .union(char_range('0'..='9'))
+///     .process("c0ffee!".into())
+///     .unwrap();
+/// assert_eq!(parsed, "c");
+/// assert_eq!(state.as_input().as_inner(), "0ffee!");
+/// ```
+pub fn char_range(range: RangeInclusive<char>) -> CharClass {
+    CharClass::from_ranges([range])
+}
+
+impl<I: Underlying, E: CustomError, C> Parser<I, Input<I>, E, C> for CharClass {
+    fn process(&self, state: State<I, E, C>) -> Result<I, Input<I>, E, C> {
+        glyph(
+            state,
+            |c| self.contains(c),
+            ExpectedError::Class(Box::new(self.clone())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::errors::{Error, ErrorKind};
+
+    #[test]
+    fn can_parse_satisfy() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            satisfy(|c| c == 'x').process("xy".into()).unwrap();
+        assert_eq!(parsed, "x");
+        assert_eq!(state.as_input(), &"y");
+        assert!(!state.is_err());
+
+        let state: State<&str> = satisfy(|c| c == 'x').process("yx".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Satisfy),
+                Input::new_with_span("yx", 0..1)
+            )
+        );
+    }
+
+    #[test]
+    fn can_parse_one_of() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            one_of("+-*/").process("-1".into()).unwrap();
+        assert_eq!(parsed, "-");
+        assert_eq!(state.as_input(), &"1");
+
+        let state: State<&str> = one_of("+-*/").process("1".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Class(Box::new(one_of("*+-/")))),
+                Input::new_with_span("1", 0..1)
+            )
+        );
+        assert_eq!(
+            state.errors().to_string(),
+            "expected a character in [*+\\-/]"
+        );
+
+        // End of input never matches
+        let state: State<&str> = one_of("+").process("".into()).unwrap_err();
+        assert!(state.is_err());
+    }
+
+    #[test]
+    fn can_parse_none_of() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            none_of("\"\\").process("ab".into()).unwrap();
+        assert_eq!(parsed, "a");
+        assert_eq!(state.as_input(), &"b");
+
+        let state: State<&str> = none_of("\"\\").process("\\n".into()).unwrap_err();
+        assert_eq!(
+            state.errors().to_string(),
+            "expected a character in [^\"\\\\]"
+        );
+
+        let state: State<&str> = none_of("\"").process("".into()).unwrap_err();
+        assert!(state.is_err());
+    }
+
+    #[cfg(not(feature = "unicode"))]
+    #[test]
+    fn classes_match_bytes() {
+        let state: State<&str> = one_of("é").process("é".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Class(Box::new(one_of("é")))),
+                Input::new_with_span("é", 0..1)
+            )
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn classes_match_chars() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            one_of("éü").process("üé".into()).unwrap();
+        assert_eq!(parsed, "ü");
+        assert_eq!(state.as_input(), &"é");
+
+        let (_, parsed): (State<&str>, Input<&str>) = none_of("a").process("😊".into()).unwrap();
+        assert_eq!(parsed, "😊");
+
+        let (_, parsed): (State<&str>, Input<&str>) =
+            satisfy(|c| c == 'Ж').process("Ж".into()).unwrap();
+        assert_eq!(parsed, "Ж");
+    }
+}
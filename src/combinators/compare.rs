@@ -1,6 +1,6 @@
 use crate::parser::{
     errors::{CustomError, Error, ErrorKind, ExpectedError},
-    input::{Input, Underlying},
+    input::{decode_glyph, Input, Underlying},
     state::State,
     Parser,
 };
@@ -20,8 +20,8 @@ use crate::parser::{
 /// assert_eq!(parsed, "te");
 /// assert_eq!(state.as_input().as_inner(), "st");
 /// ```
-pub fn is<I: Underlying, E: CustomError>(matches: I) -> impl Parser<I, Input<I>, E> {
-    move |mut state: State<I, E>| {
+pub fn is<I: Underlying, E: CustomError, C>(matches: I) -> impl Parser<I, Input<I>, E, C> {
+    move |mut state: State<I, E, C>| {
         let mut matches_input = Input::new(matches.fork());
         let input = state.as_input_mut();
         let original_input = input.fork();
@@ -50,6 +50,73 @@ pub fn is<I: Underlying, E: CustomError>(matches: I) -> impl Parser<I, Input<I>,
     }
 }
 
+/// Parses an input if it matches the given input, ignoring case. If it does, it returns the input
+/// as it appeared in the source (not as given). If not, it errors out.
+///
+/// NOTE: When `unicode` is enabled, characters are compared using Unicode simple case folding
+/// (e.g. `ẞ` matches `ß`, and `K` (Kelvin) matches `k`). Otherwise, only ASCII letters are folded.
+///
+/// ```
+/// # use errgonomic::combinators::is_no_case;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// let (state, parsed): (State<&str>, Input<&str>) = is_no_case("select").process("SeLeCt *".into()).unwrap();
+/// assert_eq!(parsed, "SeLeCt");
+/// assert_eq!(state.as_input().as_inner(), " *");
+/// ```
+pub fn is_no_case<I: Underlying, E: CustomError, C>(matches: I) -> impl Parser<I, Input<I>, E, C> {
+    move |state: State<I, E, C>| {
+        let input = state.as_input().fork();
+        let bytes = input.as_bytes();
+        let mut remaining = matches
+            .byte_span(0, matches.len())
+            .expect("the matching input to cover itself");
+        let mut matched_len = 0;
+
+        while let Some((match_c, match_len)) = decode_glyph(remaining) {
+            match decode_glyph(&bytes[matched_len..]) {
+                Some((input_c, input_len)) if fold_case(input_c) == fold_case(match_c) => {
+                    matched_len += input_len; // Update the input to the next character
+                    remaining = &remaining[match_len..]; // ... and the matching input too
+                }
+                Some((_, input_len)) => {
+                    return Err(state.with_error(Error::new(
+                        ErrorKind::expected(ExpectedError::IsNoCase(matches.fork())),
+                        input.take(matched_len + input_len),
+                    )));
+                }
+                None => {
+                    return Err(state.with_error(Error::new(
+                        ErrorKind::expected(ExpectedError::IsNoCase(matches.fork())),
+                        input.skip(matched_len),
+                    )));
+                }
+            }
+        }
+
+        Ok((
+            state.with_input(input.skip(matched_len)),
+            input.take(matched_len),
+        ))
+    }
+}
+
+/// Folds the case of `c`, for `is_no_case`.
+#[inline]
+fn fold_case(c: char) -> char {
+    #[cfg(not(feature = "unicode"))]
+    {
+        c.to_ascii_lowercase()
+    }
+    #[cfg(feature = "unicode")]
+    {
+        unicode_case_mapping::case_folded(c)
+            .and_then(|folded| char::from_u32(folded.get()))
+            .unwrap_or(c)
+    }
+}
+
 /// Inverts the result of the parser. That is to say, if the parser is successful, it will return
 /// an error with the output. If the parser is not successful, it will return the state as-is. If
 /// the parser consumes any input, it will return the state before the input was consumed.
@@ -64,8 +131,10 @@ pub fn is<I: Underlying, E: CustomError>(matches: I) -> impl Parser<I, Input<I>,
 /// let (state, _): (State<&str>, ()) = not(is("st")).process("test".into()).unwrap();
 /// assert_eq!(state.as_input().as_inner(), "test");
 /// ```
-pub fn not<I: Underlying, O, E: CustomError, P: Parser<I, O, E>>(p: P) -> impl Parser<I, (), E> {
-    move |state: State<I, E>| match p.process(state.fork()) {
+pub fn not<I: Underlying, O, E: CustomError, C, P: Parser<I, O, E, C>>(
+    p: P,
+) -> impl Parser<I, (), E, C> {
+    move |state: State<I, E, C>| match p.process(state.fork()) {
         Ok((new_state, _)) => {
             let found = state.as_input().fork().subtract(new_state.as_input());
             Err(state.with_error(Error::new(
@@ -98,7 +167,7 @@ mod tests {
         assert!(!state.is_err());
         assert_eq!(state.errors().len(), 0);
 
-        let result: Input<&str> = is::<_, DummyError>("test").parse("test123").unwrap();
+        let result: Input<&str> = is::<_, DummyError, ()>("test").parse("test123").unwrap();
         assert_eq!(result, "test");
 
         let state: State<&str> = is("test").process("123test".into()).unwrap_err();
@@ -124,6 +193,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_parse_with_is_no_case() {
+        let (state, parsed): (State<&str>, Input<&str>) = is_no_case("SELECT")
+            .process("select * from".into())
+            .unwrap();
+        assert_eq!(parsed, "select");
+        assert_eq!(state.as_input(), &" * from");
+        assert!(!state.is_err());
+
+        let result: Input<&str> = is_no_case::<_, DummyError, ()>("Content-Type")
+            .parse("content-type")
+            .unwrap();
+        assert_eq!(result, "content-type");
+
+        let state: State<&str> = is_no_case("SELECT").process("selext".into()).unwrap_err();
+        assert_eq!(state.as_input(), &"selext");
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::IsNoCase("SELECT")),
+                Input::new_with_span("selext", 0..5)
+            )
+        );
+        assert_eq!(
+            state.errors().to_string(),
+            "expected `SELECT` (case-insensitive)"
+        );
+
+        let state: State<&str> = is_no_case("SELECT").process("sel".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::IsNoCase("SELECT")),
+                Input::new_with_span("sel", 3..3)
+            )
+        );
+    }
+
+    #[cfg(not(feature = "unicode"))]
+    #[test]
+    fn is_no_case_only_folds_ascii() {
+        let state: State<&str> = is_no_case("ÉTÉ").process("été".into()).unwrap_err();
+        assert!(state.is_err());
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn can_parse_unicode_with_is_no_case() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            is_no_case("ÉTÉ").process("été!".into()).unwrap();
+        assert_eq!(parsed, "été");
+        assert_eq!(state.as_input(), &"!");
+
+        // The Kelvin sign folds to `k`, but is 3 bytes long; the output is as in the source.
+        let (state, parsed): (State<&str>, Input<&str>) =
+            is_no_case("kb").process("\u{212A}B".into()).unwrap();
+        assert_eq!(parsed, "\u{212A}B");
+        assert_eq!(state.as_input(), &"");
+
+        let (_, parsed): (State<&str>, Input<&str>) =
+            is_no_case("straße").process("STRAẞE".into()).unwrap();
+        assert_eq!(parsed, "STRAẞE");
+    }
+
     #[test]
     fn can_parse_not() {
         let state: State<&str> = not(is("te")).process("test".into()).unwrap_err();
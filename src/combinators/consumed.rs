@@ -6,10 +6,10 @@ use crate::parser::{
 };
 
 /// Gets the input consumed by the parser and returns it as the output.
-pub fn consumed<I: Underlying, O, E: CustomError, P: Parser<I, O, E>>(
+pub fn consumed<I: Underlying, O, E: CustomError, C, P: Parser<I, O, E, C>>(
     p: P,
-) -> impl Parser<I, Input<I>, E> {
-    move |state: State<I, E>| {
+) -> impl Parser<I, Input<I>, E, C> {
+    move |state: State<I, E, C>| {
         let (new_state, _) = p.process(state.fork())?;
         let found = state.as_input().subtract(new_state.as_input());
 
@@ -0,0 +1,284 @@
+use crate::parser::{
+    errors::{CustomError, Error, ErrorKind, ExpectedError},
+    input::Underlying,
+    state::State,
+    Parser,
+};
+use std::sync::Arc;
+
+/// Gets the user-defined context (see `State::with_context`), without consuming any input. If
+/// there is no context, its default is returned instead.
+///
+/// NOTE: The context is shared, so reading it is cheap, no matter how big it is.
+///
+/// ```
+/// # use errgonomic::combinators::context;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::errors::DummyError;
+/// # use std::sync::Arc;
+/// type S<'a> = State<&'a str, DummyError, usize>;
+///
+/// let state: S = State::new("x").with_context(3);
+/// let (_, depth): (S, Arc<usize>) = context().process(state).unwrap();
+/// assert_eq!(*depth, 3);
+///
+/// let (_, depth): (S, Arc<usize>) = context().process("x".into()).unwrap();
+/// assert_eq!(*depth, 0);
+/// ```
+pub fn context<C: Default, I: Underlying, E: CustomError>() -> impl Parser<I, Arc<C>, E, C> {
+    move |state: State<I, E, C>| {
+        let context = state.shared_context().unwrap_or_default();
+        Ok((state, context))
+    }
+}
+
+/// Parses `p`, then updates the user-defined context (or its default, if there is none) with its
+/// output, e.g. to declare a name. As the state is forked whenever a parser
+/// backtracks, an update is undone if the parser that made it is.
+///
+/// ```
+/// # use errgonomic::combinators::{update_context, context, identifier, is, any};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::errors::DummyError;
+/// type S<'a> = State<&'a str, DummyError, Vec<&'a str>>;
+///
+/// let declare = update_context(
+///     is("let ").then(identifier()).map(|(_, name)| name.as_inner()),
+///     |names: &Vec<&str>, name| [names.as_slice(), &[*name]].concat(),
+/// );
+///
+/// let (state, _): (S, _) = declare.process("let x".into()).unwrap();
+/// assert_eq!(state.context(), Some(&vec!["x"]));
+///
+/// // The declaration was backtracked, so is the update
+/// let (state, _): (S, Input<&str>) = any((declare.then(is("!")).map(|_| unreachable!()), is("let y")))
+///     .process("let y?".into())
+///     .unwrap();
+/// assert_eq!(state.context(), None);
+/// ```
+pub fn update_context<I: Underlying, O, E: CustomError, C, P, F>(
+    p: P,
+    f: F,
+) -> impl Parser<I, O, E, C>
+where
+    C: Default,
+    P: Parser<I, O, E, C>,
+    F: Fn(&C, &O) -> C,
+{
+    move |state: State<I, E, C>| {
+        let (state, output) = p.process(state)?;
+        let context = state.shared_context().unwrap_or_default();
+        let context = f(&context, &output);
+        Ok((state.with_shared_context(Arc::new(context)), output))
+    }
+}
+
+/// Parses `p` with the user-defined context changed by `f`, then restores it afterwards, e.g. to
+/// track the nesting depth, or a scope of names that are only visible inside `p`.
+///
+/// ```
+/// # use errgonomic::combinators::{scoped_context, context, between, is, maybe};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::errors::{DummyError, Result};
+/// # use std::sync::Arc;
+/// // The depth of the innermost `()`
+/// fn depth(state: State<&str, DummyError, usize>) -> Result<&str, usize, DummyError, usize> {
+///     scoped_context(
+///         |depth: &usize| depth + 1,
+///         between(is("("), maybe(depth), is(")")).then(context()),
+///     )
+///     .map(|(inner, depth): (_, Arc<usize>)| inner.unwrap_or(*depth))
+///     .process(state)
+/// }
+///
+/// let (state, parsed) = depth.process("((()))".into()).unwrap();
+/// assert_eq!(parsed, 3);
+/// assert_eq!(state.context(), None);
+/// ```
+pub fn scoped_context<I: Underlying, O, E: CustomError, C, F, P>(
+    f: F,
+    p: P,
+) -> impl Parser<I, O, E, C>
+where
+    C: Default,
+    F: Fn(&C) -> C,
+    P: Parser<I, O, E, C>,
+{
+    move |state: State<I, E, C>| {
+        let outer = state.shared_context();
+        let scoped = f(&outer.clone().unwrap_or_default());
+        let (state, output) = p.process(state.with_shared_context(Arc::new(scoped)))?;
+
+        let state = match outer {
+            Some(outer) => state.with_shared_context(outer),
+            None => state.without_context(),
+        };
+        Ok((state, output))
+    }
+}
+
+/// Parses `p`, and checks its output against the user-defined context (or its default, if there
+/// is none), e.g. that a name was declared. Like `Parser::verify`, a `Verified(label)`
+/// error is returned over exactly what `p` matched if the check fails, and no input is consumed.
+///
+/// ```
+/// # use errgonomic::combinators::{verify_context, identifier};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::errors::DummyError;
+/// type S<'a> = State<&'a str, DummyError, Vec<&'a str>>;
+///
+/// let type_name = verify_context(
+///     identifier(),
+///     |types: &Vec<&str>, name: &Input<&str>| types.contains(&name.as_inner()),
+///     "a type name",
+/// );
+///
+/// let state: S = State::new("uint").with_context(vec!["uint"]);
+/// let (_, parsed) = type_name.process(state).unwrap();
+/// assert_eq!(parsed, "uint");
+///
+/// let state: S = type_name.process("x".into()).unwrap_err();
+/// assert_eq!(state.errors().to_string(), "expected a type name");
+/// ```
+pub fn verify_context<I: Underlying, O, E: CustomError, C, P, F>(
+    p: P,
+    f: F,
+    label: &'static str,
+) -> impl Parser<I, O, E, C>
+where
+    C: Default,
+    P: Parser<I, O, E, C>,
+    F: Fn(&C, &O) -> bool,
+{
+    move |state: State<I, E, C>| {
+        let (new_state, output) = p.process(state.fork())?;
+        let context = new_state.shared_context().unwrap_or_default();
+        if f(&context, &output) {
+            return Ok((new_state, output));
+        }
+
+        let found = state.as_input().fork().subtract(new_state.as_input());
+        Err(state.with_error(Error::new(
+            ErrorKind::expected(ExpectedError::Verified(label)),
+            found,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        combinators::{any, identifier, is, many, whitespace_wrapped as ww},
+        parser::{errors::DummyError, input::Input},
+    };
+
+    #[derive(Debug, PartialEq)]
+    enum Statement<'a> {
+        Typedef(&'a str),
+        Declaration(&'a str, &'a str),
+        Multiplication(&'a str, &'a str),
+    }
+
+    /// Parses `;`-terminated statements, with the declared type names as the context. `a * b` is a
+    /// declaration if `a` is a type, and a multiplication otherwise.
+    fn statements<'a>(input: &'a str) -> (Vec<Statement<'a>>, Vec<&'a str>) {
+        let name = || ww(identifier()).map(|n: Input<&'a str>| n.as_inner());
+        let typedef = update_context(
+            ww(is("typedef")).then(name()).map(|(_, n)| n),
+            |types: &Vec<&'a str>, n| [types.as_slice(), &[*n]].concat(),
+        )
+        .map(Statement::Typedef);
+        let type_name = verify_context(name(), |types: &Vec<&str>, n| types.contains(n), "type");
+        let declaration = type_name
+            .then(is("*"))
+            .then(name())
+            .map(|((t, _), n)| Statement::Declaration(t, n));
+        let multiplication = name()
+            .then(is("*"))
+            .then(name())
+            .map(|((a, _), b)| Statement::Multiplication(a, b));
+        let statement = any((typedef, declaration, multiplication)).then(is(";"));
+
+        let (state, parsed): (State<&'a str, DummyError, Vec<&'a str>>, _) =
+            many(statement.map(|(s, _)| s))
+                .process(input.into())
+                .unwrap();
+        (parsed, state.context().cloned().unwrap_or_default())
+    }
+
+    #[test]
+    fn can_parse_typedef_names() {
+        // The input (and so the names in the context) isn't `'static`
+        let input = String::from("a * b; typedef a; a * b; c * d;");
+        let (parsed, types) = statements(&input);
+        assert_eq!(
+            parsed,
+            [
+                Statement::Multiplication("a", "b"),
+                Statement::Typedef("a"),
+                Statement::Declaration("a", "b"),
+                Statement::Multiplication("c", "d"),
+            ]
+        );
+        assert_eq!(types, ["a"]);
+    }
+
+    /// Parses a here-doc, i.e. `<<END` followed by a body closed by the same terminator, with the
+    /// terminator as the context.
+    fn heredoc<'a>(input: &'a str) -> &'a str {
+        let open = update_context(
+            is("<<")
+                .then(identifier())
+                .map(|(_, t): (_, Input<&'a str>)| t.as_inner()),
+            |_: &&'a str, t: &&'a str| *t,
+        );
+        let body = |state: State<&'a str, DummyError, &'a str>| {
+            let terminator = format!("\n{}", state.context().expect("a terminator"));
+            let input = state.as_input().fork();
+            match input.as_inner().find(&terminator) {
+                Some(len) => Ok((
+                    state.with_input(input.skip(len + terminator.len())),
+                    input.take(len).as_inner(),
+                )),
+                None => Err(state),
+            }
+        };
+
+        let (_, (_, parsed)) = open.then(body).process(input.into()).unwrap();
+        parsed
+    }
+
+    #[test]
+    fn can_parse_heredocs() {
+        let input = String::from("<<EOF\na\nEND\nb\nEOF");
+        assert_eq!(heredoc(&input), "\na\nEND\nb");
+    }
+
+    #[test]
+    fn context_is_restored_on_backtracking() {
+        let increment = || update_context(is("+"), |n: &u32, _| n + 1);
+        let parser = any((
+            increment().then(increment()).then(is("!")).map(|_| ()),
+            increment().map(|_| ()),
+        ));
+
+        let (state, _): (State<&str, DummyError, u32>, ()) = parser.process("++?".into()).unwrap();
+        assert_eq!(state.context(), Some(&1));
+
+        // Scoped changes are undone, and an outer context is kept
+        let state = State::new("+").with_context(10u32);
+        let (state, n): (State<&str, DummyError, u32>, Arc<u32>) =
+            scoped_context(|n: &u32| n * 2, increment().then(context()).map(|(_, n)| n))
+                .process(state)
+                .unwrap();
+        assert_eq!(*n, 21);
+        assert_eq!(state.context(), Some(&10));
+    }
+}
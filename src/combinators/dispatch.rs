@@ -0,0 +1,325 @@
+use super::CharClass;
+use crate::parser::{
+    errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
+    input::{decode_glyph, Underlying},
+    state::State,
+    Parser,
+};
+
+/// The set of characters a parser can start with, so that it can be used as a `Dispatch` branch
+/// without spelling its class out.
+///
+/// NOTE: Parsers that can match without consuming anything have no meaningful first set, and
+/// should be used as a `Dispatch::fallback` instead.
+pub trait FirstSet {
+    /// Gets the class of every character the parser can start with.
+    fn first_set(&self) -> CharClass;
+}
+
+impl FirstSet for CharClass {
+    fn first_set(&self) -> CharClass {
+        self.clone()
+    }
+}
+
+type PBranch<'a, I, O, E, C> = Box<dyn Parser<I, O, E, C> + 'a>;
+
+/// Creates an empty dispatch table. See `Dispatch`.
+///
+/// ```
+/// # use errgonomic::combinators::{dispatch, decimal, is, one_of, literal_map};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Value { Null, Bool(bool), Number(u32), Array }
+///
+/// let value = dispatch()
+///     .on(one_of("["), is("[]").map(|_| Value::Array))
+///     .on(one_of("0123456789"), decimal.map(|n: Input<&str>| Value::Number(n.as_inner().parse().unwrap())))
+///     .branch(literal_map([("true", Value::Bool(true)), ("false", Value::Bool(false))]))
+///     .branch(literal_map([("null", Value::Null)]));
+///
+/// let (_, parsed): (State<&str>, Value) = value.process("42".into()).unwrap();
+/// assert_eq!(parsed, Value::Number(42));
+/// let (_, parsed): (State<&str>, Value) = value.process("false".into()).unwrap();
+/// assert_eq!(parsed, Value::Bool(false));
+/// ```
+pub fn dispatch<'a, I: Underlying, O, E: CustomError, C>() -> Dispatch<'a, I, O, E, C> {
+    Dispatch {
+        branches: vec![],
+        ascii: vec![vec![]; 128],
+        first: CharClass::from_ranges([]),
+        fallback: None,
+    }
+}
+
+/// A dispatch table, which peeks at the next character and only tries the branches that can start
+/// with it, instead of trying every alternative like `any` does.
+///
+/// Where branches overlap, the matching ones are tried in the order they were added (ordered
+/// choice), and their errors are returned as a single `All` error if they all fail. If no branch
+/// can start with the next character, the `fallback` is tried, if there is one. Otherwise, an
+/// error describing every character a branch could have started with is returned.
+pub struct Dispatch<'a, I: Underlying, O, E: CustomError, C = ()> {
+    /// The branches, each with the class of characters that select it.
+    branches: Vec<(CharClass, PBranch<'a, I, O, E, C>)>,
+
+    /// The indices of the branches each ASCII character selects, so the common case is a lookup.
+    ascii: Vec<Vec<usize>>,
+
+    /// The union of the classes of all branches, for errors.
+    first: CharClass,
+
+    /// The parser to try if no branch is selected.
+    fallback: Option<PBranch<'a, I, O, E, C>>,
+}
+
+impl<'a, I: Underlying, O, E: CustomError, C> Dispatch<'a, I, O, E, C> {
+    /// Adds a branch, which is tried when the next character is in `class`.
+    pub fn on<P: Parser<I, O, E, C> + 'a>(mut self, class: CharClass, p: P) -> Self {
+        let i = self.branches.len();
+        for c in 0..128u8 {
+            if class.contains(c as char) {
+                self.ascii[c as usize].push(i);
+            }
+        }
+
+        self.first = self.first.union(class.clone());
+        self.branches.push((class, Box::new(p)));
+        self
+    }
+
+    /// Adds a branch, which is tried when the next character is in the first set of `p`.
+    pub fn branch<P: Parser<I, O, E, C> + FirstSet + 'a>(self, p: P) -> Self {
+        let class = p.first_set();
+        self.on(class, p)
+    }
+
+    /// Sets the parser to try when no branch is selected, including at the end of the input.
+    pub fn fallback<P: Parser<I, O, E, C> + 'a>(mut self, p: P) -> Self {
+        self.fallback = Some(Box::new(p));
+        self
+    }
+
+    /// INTERNAL: Tries each of the `selected` branches, then the fallback (only if no branch was
+    /// selected), like `any`.
+    fn try_branches<S: Iterator<Item = usize>>(
+        &self,
+        state: State<I, E, C>,
+        selected: S,
+    ) -> Result<I, O, E, C> {
+        let mut errs = vec![];
+
+        for i in selected {
+            match self.branches[i].1.process(state.fork()) {
+                Ok(x) => return Ok(x),
+                Err(e) if e.errors().is_committed() => return Err(e),
+                Err(e) => errs.push(e.errors().clone()),
+            }
+        }
+
+        if errs.is_empty() {
+            if let Some(fallback) = &self.fallback {
+                return fallback.process(state);
+            }
+
+            let input = state.as_input().fork();
+            let len = decode_glyph(input.as_bytes()).map_or(1, |(_, len)| len);
+            return Err(state.with_error(Error::new(
+                ErrorKind::expected(ExpectedError::Class(Box::new(self.first.clone()))),
+                input.take(len),
+            )));
+        }
+
+        let input = errs
+            .iter()
+            .map(|err| err.from())
+            .reduce(|acc, x| acc.join_between(&x))
+            .expect("There to be at least 1 error");
+
+        Err(state.with_error(Error::new(ErrorKind::all(errs), input)))
+    }
+}
+
+impl<I: Underlying, O, E: CustomError, C> Parser<I, O, E, C> for Dispatch<'_, I, O, E, C> {
+    fn process(&self, state: State<I, E, C>) -> Result<I, O, E, C> {
+        match decode_glyph(state.as_input().as_bytes()) {
+            Some((c, _)) if c.is_ascii() => {
+                let selected = self.ascii[c as usize].iter().copied();
+                self.try_branches(state, selected)
+            }
+            Some((c, _)) => {
+                let selected = (0..self.branches.len()).filter(|&i| self.branches[i].0.contains(c));
+                self.try_branches(state, selected)
+            }
+            None => self.try_branches(state, core::iter::empty()),
+        }
+    }
+}
+
+/// INTERNAL: The first set of a list of literals, i.e. the class of their first characters. Empty
+/// literals match anything.
+pub(crate) fn literals_first_set<'b, L: IntoIterator<Item = &'b [u8]>>(literals: L) -> CharClass {
+    let mut firsts = vec![];
+
+    for bytes in literals {
+        match decode_glyph(bytes) {
+            Some((c, _)) => firsts.push(c..=c),
+            None => return CharClass::from_ranges([]).negate(),
+        }
+    }
+
+    CharClass::from_ranges(firsts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        combinators::{commit, decimal, is, keyword, literals, one_of},
+        parser::input::Input,
+    };
+    use std::cell::Cell;
+
+    #[test]
+    fn dispatches_on_first_char() {
+        let parser = dispatch()
+            .on(one_of("0123456789"), decimal)
+            .on(one_of("\""), is("\"\""))
+            .branch(literals(["true", "false"]));
+
+        let (state, parsed): (State<&str>, Input<&str>) = parser.process("12,".into()).unwrap();
+        assert_eq!(parsed, "12");
+        assert_eq!(state.as_input(), &",");
+
+        let (_, parsed): (State<&str>, Input<&str>) = parser.process("\"\"".into()).unwrap();
+        assert_eq!(parsed, "\"\"");
+
+        let (_, parsed): (State<&str>, Input<&str>) = parser.process("true".into()).unwrap();
+        assert_eq!(parsed, "true");
+    }
+
+    #[test]
+    fn only_tries_selected_branches() {
+        /// Parses `x`, counting how many times it was tried.
+        struct Counting<'c>(&'c Cell<usize>);
+
+        impl Parser<&'static str, Input<&'static str>> for Counting<'_> {
+            fn process(
+                &self,
+                state: State<&'static str>,
+            ) -> Result<&'static str, Input<&'static str>> {
+                self.0.set(self.0.get() + 1);
+                is("x").process(state)
+            }
+        }
+
+        let tried = Cell::new(0);
+        let counting = Counting(&tried);
+
+        let parser = dispatch()
+            .on(one_of("x"), counting)
+            .on(one_of("y"), is("y"));
+        let _: (State<&str>, Input<&str>) = parser.process("y".into()).unwrap();
+        assert_eq!(tried.get(), 0);
+        let _: (State<&str>, Input<&str>) = parser.process("x".into()).unwrap();
+        assert_eq!(tried.get(), 1);
+    }
+
+    #[test]
+    fn overlapping_branches_are_ordered_choice() {
+        let parser = dispatch()
+            .on(one_of("a"), is("abc"))
+            .on(one_of("ab"), is("ab"))
+            .on(one_of("a"), is("a"));
+
+        let (_, parsed): (State<&str>, Input<&str>) = parser.process("abc".into()).unwrap();
+        assert_eq!(parsed, "abc");
+        let (_, parsed): (State<&str>, Input<&str>) = parser.process("abd".into()).unwrap();
+        assert_eq!(parsed, "ab");
+        let (_, parsed): (State<&str>, Input<&str>) = parser.process("ax".into()).unwrap();
+        assert_eq!(parsed, "a");
+
+        let state: State<&str> = parser.process("b".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::All(vec![Error::new(
+                    ErrorKind::expected(ExpectedError::Is("ab")),
+                    Input::new_with_span("b", 0..1)
+                )]),
+                Input::new_with_span("b", 0..1)
+            )
+        );
+    }
+
+    #[test]
+    fn committed_branches_stop_dispatch() {
+        let parser = dispatch()
+            .on(one_of("a"), commit(is("ab")))
+            .on(one_of("a"), is("ac"));
+
+        let state: State<&str> = parser.process("ac".into()).unwrap_err();
+        assert!(state.errors().is_committed());
+    }
+
+    #[test]
+    fn dispatch_falls_back() {
+        let parser = dispatch().branch(keyword("if")).fallback(is("else"));
+
+        let (_, parsed): (State<&str>, Input<&str>) = parser.process("else".into()).unwrap();
+        assert_eq!(parsed, "else");
+
+        // A selected branch failing doesn't fall back
+        let state: State<&str> = parser.process("iffy".into()).unwrap_err();
+        assert!(state.is_err());
+
+        let parser = dispatch().on(one_of("x"), is("x")).fallback(is(""));
+        let (state, parsed): (State<&str>, Input<&str>) = parser.process("".into()).unwrap();
+        assert_eq!(parsed, "");
+        assert!(!state.is_err());
+    }
+
+    #[test]
+    fn dispatch_reports_first_set() {
+        let parser = dispatch()
+            .on(one_of("["), is("[]"))
+            .branch(literals(["true", "false"]));
+
+        let state: State<&str> = parser.process("x".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Class(Box::new(one_of("[ft")))),
+                Input::new_with_span("x", 0..1)
+            )
+        );
+        assert_eq!(
+            state.errors().to_string(),
+            "expected a character in [\\[ft]"
+        );
+
+        let state: State<&str> = parser.process("".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Class(Box::new(one_of("[ft")))),
+                Input::new_with_span("", 0..0)
+            )
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn dispatches_on_unicode_chars() {
+        let parser = dispatch()
+            .on(one_of("λ"), is("λx"))
+            .branch(literals(["→", "=>"]));
+
+        let (_, parsed): (State<&str>, Input<&str>) = parser.process("→".into()).unwrap();
+        assert_eq!(parsed, "→");
+        let (_, parsed): (State<&str>, Input<&str>) = parser.process("λx".into()).unwrap();
+        assert_eq!(parsed, "λx");
+    }
+}
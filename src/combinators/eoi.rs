@@ -9,9 +9,9 @@ use crate::parser::{
 /// # use errgonomic::combinators::eoi;
 /// # use errgonomic::parser::Parser;
 /// # use errgonomic::parser::errors::DummyError;
-/// assert_eq!(eoi::<_, DummyError>.parse("").unwrap(), ());
+/// assert_eq!(eoi::<_, DummyError, ()>.parse("").unwrap(), ());
 /// ```
-pub fn eoi<I: Underlying, E: CustomError>(state: State<I, E>) -> Result<I, (), E> {
+pub fn eoi<I: Underlying, E: CustomError, C>(state: State<I, E, C>) -> Result<I, (), E, C> {
     if state.as_input().is_empty() {
         Ok((state, ()))
     } else {
@@ -0,0 +1,755 @@
+use crate::parser::{
+    errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
+    input::{decode_glyph, Input, Underlying},
+    state::State,
+    Parser,
+};
+use core::{
+    ops::{Mul, Neg},
+    str::FromStr,
+};
+
+/// A primitive floating-point type that `float` can parse into.
+pub trait Float: Copy + FromStr + Mul<Output = Self> + Neg<Output = Self> {
+    /// The number of significant bits, including the implicit one (see `f64::MANTISSA_DIGITS`).
+    const MANTISSA_DIGITS: u32;
+
+    /// One more than the smallest normal exponent (see `f64::MIN_EXP`).
+    const MIN_EXP: i32;
+
+    /// One more than the largest exponent (see `f64::MAX_EXP`).
+    const MAX_EXP: i32;
+
+    /// Positive zero.
+    const ZERO: Self;
+
+    /// Positive infinity.
+    const INFINITY: Self;
+
+    /// Not a number.
+    const NAN: Self;
+
+    /// Converts an integer with at most `MANTISSA_DIGITS` significant bits (so that it's exact).
+    fn from_u64(n: u64) -> Self;
+
+    /// Gets `2^exp`, for an `exp` in the normal range (`MIN_EXP - 1..MAX_EXP`).
+    fn pow2(exp: i32) -> Self;
+}
+
+impl Float for f32 {
+    const MANTISSA_DIGITS: u32 = f32::MANTISSA_DIGITS;
+    const MIN_EXP: i32 = f32::MIN_EXP;
+    const MAX_EXP: i32 = f32::MAX_EXP;
+    const ZERO: Self = 0.0;
+    const INFINITY: Self = f32::INFINITY;
+    const NAN: Self = f32::NAN;
+
+    #[inline]
+    fn from_u64(n: u64) -> Self {
+        n as f32
+    }
+
+    #[inline]
+    fn pow2(exp: i32) -> Self {
+        f32::from_bits(((exp + f32::MAX_EXP - 1) as u32) << (f32::MANTISSA_DIGITS - 1))
+    }
+}
+
+impl Float for f64 {
+    const MANTISSA_DIGITS: u32 = f64::MANTISSA_DIGITS;
+    const MIN_EXP: i32 = f64::MIN_EXP;
+    const MAX_EXP: i32 = f64::MAX_EXP;
+    const ZERO: Self = 0.0;
+    const INFINITY: Self = f64::INFINITY;
+    const NAN: Self = f64::NAN;
+
+    #[inline]
+    fn from_u64(n: u64) -> Self {
+        n as f64
+    }
+
+    #[inline]
+    fn pow2(exp: i32) -> Self {
+        f64::from_bits(((exp + f64::MAX_EXP - 1) as u64) << (f64::MANTISSA_DIGITS - 1))
+    }
+}
+
+/// The grammar of the floating-point literals that `float` accepts. The default accepts
+/// everything, and `json`, `rust` and `c` match the literals of those languages (plus a sign).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FloatFormat {
+    /// Whether a leading `-` is allowed.
+    pub minus: bool,
+
+    /// Whether a leading `+` is allowed.
+    pub plus: bool,
+
+    /// Whether the integer part can be left out, e.g. `.5`.
+    pub leading_dot: bool,
+
+    /// Whether the fractional part can be left out after the `.`, e.g. `5.`.
+    pub trailing_dot: bool,
+
+    /// Whether there can be an exponent, e.g. `5e-3`.
+    pub exponent: bool,
+
+    /// Whether a number without a `.` or exponent (i.e. an integer) is allowed, e.g. `5`.
+    pub integers: bool,
+
+    /// Whether the integer part can have leading zeros, e.g. `05.0`.
+    pub leading_zeros: bool,
+
+    /// Whether `inf`, `infinity` and `nan` are allowed, in any case.
+    pub special: bool,
+
+    /// Whether hexadecimal floats are allowed, e.g. `0x1.8p3`. The binary exponent is required.
+    pub hex: bool,
+
+    /// Whether digits can be separated by `_` (but can't start with one), e.g. `1_000.0`.
+    pub separators: bool,
+}
+
+impl FloatFormat {
+    /// JSON numbers, e.g. `-1.5e3`. There is no `+`, and no leading zeros.
+    pub const fn json() -> Self {
+        Self {
+            minus: true,
+            plus: false,
+            leading_dot: false,
+            trailing_dot: false,
+            exponent: true,
+            integers: true,
+            leading_zeros: false,
+            special: false,
+            hex: false,
+            separators: false,
+        }
+    }
+
+    /// Rust float literals (without a type suffix), e.g. `1_000.`, plus a sign.
+    pub const fn rust() -> Self {
+        Self {
+            minus: true,
+            plus: true,
+            leading_dot: false,
+            trailing_dot: true,
+            exponent: true,
+            integers: false,
+            leading_zeros: true,
+            special: false,
+            hex: false,
+            separators: true,
+        }
+    }
+
+    /// C float literals (without a type suffix), e.g. `.5`, `5.` or `0x1p-3`, plus a sign.
+    pub const fn c() -> Self {
+        Self {
+            minus: true,
+            plus: true,
+            leading_dot: true,
+            trailing_dot: true,
+            exponent: true,
+            integers: false,
+            leading_zeros: true,
+            special: false,
+            hex: true,
+            separators: false,
+        }
+    }
+}
+
+impl Default for FloatFormat {
+    fn default() -> Self {
+        Self {
+            minus: true,
+            plus: true,
+            leading_dot: true,
+            trailing_dot: true,
+            exponent: true,
+            integers: true,
+            leading_zeros: true,
+            special: true,
+            hex: true,
+            separators: true,
+        }
+    }
+}
+
+/// Parses a floating-point literal into an `f32` or `f64`, correctly rounded. By default, any
+/// literal is accepted: use `FloatParser::format` to change the grammar (e.g. to `FloatFormat::json`),
+/// and `FloatParser::recognize` to only get the literal, without converting it.
+///
+/// ```
+/// # use errgonomic::combinators::{float, FloatFormat};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::errors::DummyError;
+/// let (state, parsed): (State<&str>, f64) = float().process("-1_000.5e-1;".into()).unwrap();
+/// assert_eq!(parsed, -100.05);
+/// assert_eq!(state.as_input().as_inner(), ";");
+///
+/// assert_eq!(float::<f32, _, DummyError, ()>().parse("0x1.8p1"), Ok(3.0));
+/// assert!(float::<f64, _, DummyError, ()>().format(FloatFormat::json()).parse(".5").is_err());
+/// ```
+pub fn float<T: Float, I: Underlying, E: CustomError, C>() -> FloatParser<T, I, E, C> {
+    FloatParser {
+        format: FloatFormat::default(),
+        _marker: std::marker::PhantomData,
+    }
+}
+
+/// A floating-point literal parser. See `float`.
+#[derive(Debug, Clone, Copy)]
+pub struct FloatParser<T: Float, I: Underlying, E: CustomError, C = ()> {
+    /// The grammar of the literals.
+    format: FloatFormat,
+
+    _marker: std::marker::PhantomData<(T, I, E, C)>,
+}
+
+impl<T: Float, I: Underlying, E: CustomError, C> FloatParser<T, I, E, C> {
+    /// Sets the grammar of the literals.
+    pub fn format(mut self, format: FloatFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Only returns the literal, without converting it.
+    ///
+    /// ```
+    /// # use errgonomic::combinators::{float, FloatFormat};
+    /// # use errgonomic::parser::Parser;
+    /// # use errgonomic::parser::errors::DummyError;
+    /// let parsed = float::<f64, _, DummyError, ()>().format(FloatFormat::rust()).recognize().parse("1e9").unwrap();
+    /// assert_eq!(parsed, "1e9");
+    /// ```
+    pub fn recognize(self) -> impl Parser<I, Input<I>, E, C> {
+        move |state: State<I, E, C>| {
+            let input = state.as_input().fork();
+            match recognize(input.as_bytes(), &self.format) {
+                Ok(literal) => Ok((
+                    state.with_input(input.skip(literal.len)),
+                    input.take(literal.len),
+                )),
+                Err((start, len)) => Err(state.with_error(float_error(&input, start, len))),
+            }
+        }
+    }
+}
+
+impl<T: Float, I: Underlying, E: CustomError, C> Parser<I, T, E, C> for FloatParser<T, I, E, C> {
+    fn process(&self, state: State<I, E, C>) -> Result<I, T, E, C> {
+        let input = state.as_input().fork();
+        let bytes = input.as_bytes();
+        match recognize(bytes, &self.format) {
+            Ok(literal) => {
+                let value = literal.value::<T>(&bytes[..literal.len]);
+                Ok((state.with_input(input.skip(literal.len)), value))
+            }
+            Err((start, len)) => Err(state.with_error(float_error(&input, start, len))),
+        }
+    }
+}
+
+/// INTERNAL: The error for when there is no float, over what was recognized (or the glyph after
+/// the sign, if nothing was).
+fn float_error<I: Underlying, E: CustomError>(
+    input: &Input<I>,
+    start: usize,
+    len: usize,
+) -> Error<I, E> {
+    let span = match len {
+        0 => {
+            let len = decode_glyph(&input.as_bytes()[start..]).map_or(1, |(_, len)| len);
+            input.skip(start).take(len)
+        }
+        len => input.take(len),
+    };
+
+    Error::new(ErrorKind::expected(ExpectedError::Float), span)
+}
+
+/// INTERNAL: A recognized literal.
+struct Literal {
+    /// The length of the literal, in bytes.
+    len: usize,
+
+    /// Whether the literal starts with a `-`.
+    negative: bool,
+
+    /// What kind of literal it is.
+    kind: LiteralKind,
+}
+
+/// INTERNAL: The kinds of literal.
+enum LiteralKind {
+    Infinity,
+    Nan,
+    /// A decimal literal, which `str::parse` can convert (after removing separators).
+    Decimal,
+    /// A hexadecimal literal, with its (already converted) significand and binary exponent.
+    Hex(Significand),
+}
+
+impl Literal {
+    /// Converts the literal, given its bytes.
+    fn value<T: Float>(&self, bytes: &[u8]) -> T {
+        let value = match &self.kind {
+            LiteralKind::Infinity => T::INFINITY,
+            LiteralKind::Nan => T::NAN,
+            LiteralKind::Hex(significand) => significand.value(),
+            LiteralKind::Decimal => {
+                // NOTE: The sign is applied below, so that `-nan` and `-0` work the same everywhere.
+                let bytes = &bytes[usize::from(bytes[0] == b'-' || bytes[0] == b'+')..];
+                let parsed = match bytes.contains(&b'_') {
+                    false => core::str::from_utf8(bytes)
+                        .ok()
+                        .and_then(|s| s.parse().ok()),
+                    true => {
+                        let s = bytes
+                            .iter()
+                            .filter(|&&b| b != b'_')
+                            .map(|&b| b as char)
+                            .collect::<String>();
+                        s.parse().ok()
+                    }
+                };
+                parsed.expect("A recognized decimal literal to be accepted by `str::parse`")
+            }
+        };
+
+        if self.negative {
+            -value
+        } else {
+            value
+        }
+    }
+}
+
+/// INTERNAL: Recognizes a float literal at the start of `bytes`. On failure, returns where the
+/// number starts (after the sign), and the length of what looked like a literal, for the error.
+fn recognize(bytes: &[u8], format: &FloatFormat) -> core::result::Result<Literal, (usize, usize)> {
+    let (negative, start) = match bytes.first() {
+        Some(b'-') if format.minus => (true, 1),
+        Some(b'+') if format.plus => (false, 1),
+        _ => (false, 0),
+    };
+    let literal = |len, kind| Literal {
+        len,
+        negative,
+        kind,
+    };
+
+    if format.special {
+        for (word, kind) in [
+            ("infinity", LiteralKind::Infinity),
+            ("inf", LiteralKind::Infinity),
+            ("nan", LiteralKind::Nan),
+        ] {
+            let end = start + word.len();
+            if bytes
+                .get(start..end)
+                .is_some_and(|b| b.eq_ignore_ascii_case(word.as_bytes()))
+            {
+                return Ok(literal(end, kind));
+            }
+        }
+    }
+
+    if let Some((len, significand)) = recognize_hex(&bytes[start..], format) {
+        return Ok(literal(start + len, LiteralKind::Hex(significand)));
+    }
+
+    let digits = |i| digits_len(bytes, i, 10, format.separators);
+    let mut i = start;
+
+    let int = digits(i);
+    i += match int {
+        _ if !format.leading_zeros && bytes.get(i) == Some(&b'0') => usize::from(int > 0),
+        int => int,
+    };
+
+    let mut is_float = false;
+    if bytes.get(i) == Some(&b'.') {
+        let frac = digits(i + 1);
+        let after = bytes.get(i + 1 + frac).copied();
+        let dot = match (int > 0, frac > 0) {
+            (true, true) => true,
+            (false, true) => format.leading_dot,
+            // NOTE: Like Rust, `1.` isn't a float if it's followed by something like a method
+            // call or range (but it is if it's followed by an exponent).
+            (true, false) => {
+                format.trailing_dot
+                    && !matches!(after, Some(b'.' | b'_'))
+                    && !(after.is_some_and(|b| b.is_ascii_alphabetic())
+                        && !(format.exponent && matches!(after, Some(b'e' | b'E'))))
+            }
+            (false, false) => false,
+        };
+
+        if dot {
+            i += 1 + frac;
+            is_float = true;
+        }
+    }
+
+    if i == start {
+        return Err((start, 0));
+    }
+
+    if format.exponent && matches!(bytes.get(i), Some(b'e' | b'E')) {
+        let sign = usize::from(matches!(bytes.get(i + 1), Some(b'-' | b'+')));
+        let exp = digits(i + 1 + sign);
+        if exp > 0 {
+            i += 1 + sign + exp;
+            is_float = true;
+        }
+    }
+
+    if !is_float && !format.integers {
+        return Err((start, i));
+    }
+
+    Ok(literal(i, LiteralKind::Decimal))
+}
+
+/// INTERNAL: Recognizes a hexadecimal float (without a sign), e.g. `0x1.8p3`, if the format allows
+/// them.
+fn recognize_hex(bytes: &[u8], format: &FloatFormat) -> Option<(usize, Significand)> {
+    if !format.hex || !matches!(bytes.get(..2), Some([b'0', b'x' | b'X'])) {
+        return None;
+    }
+
+    let mut significand = Significand::default();
+    let mut i = 2;
+    let int = digits_len(bytes, i, 16, format.separators);
+    significand.push_digits(&bytes[i..i + int], false);
+    i += int;
+
+    let mut frac = 0;
+    if bytes.get(i) == Some(&b'.') {
+        frac = digits_len(bytes, i + 1, 16, format.separators);
+        if int > 0 || frac > 0 {
+            significand.push_digits(&bytes[i + 1..i + 1 + frac], true);
+            i += 1 + frac;
+        }
+    }
+
+    if (int == 0 && frac == 0) || !matches!(bytes.get(i), Some(b'p' | b'P')) {
+        return None;
+    }
+
+    let negative = bytes.get(i + 1) == Some(&b'-');
+    let sign = usize::from(matches!(bytes.get(i + 1), Some(b'-' | b'+')));
+    let exp = digits_len(bytes, i + 1 + sign, 10, format.separators);
+    if exp == 0 {
+        return None;
+    }
+
+    let exponent = bytes[i + 1 + sign..i + 1 + sign + exp]
+        .iter()
+        .filter(|&&b| b != b'_')
+        // NOTE: Saturating, as anything this big is infinite (or zero) anyway.
+        .fold(0i32, |acc, &b| {
+            acc.saturating_mul(10).saturating_add((b - b'0') as i32)
+        });
+    significand.exponent =
+        significand
+            .exponent
+            .saturating_add(if negative { -exponent } else { exponent });
+
+    Some((i + 1 + sign + exp, significand))
+}
+
+/// INTERNAL: Gets the length of the run of digits (in `radix`) at `bytes[i..]`. With `separators`,
+/// `_`s are allowed after the first digit.
+fn digits_len(bytes: &[u8], i: usize, radix: u32, separators: bool) -> usize {
+    let mut len = 0;
+
+    while let Some(&b) = bytes.get(i + len) {
+        if !((b as char).is_digit(radix) || (separators && b == b'_' && len > 0)) {
+            break;
+        }
+
+        len += 1;
+    }
+
+    len
+}
+
+/// INTERNAL: The significand of a hexadecimal float, i.e. `bits * 2^exponent`.
+#[derive(Default)]
+struct Significand {
+    /// The most significant bits.
+    bits: u64,
+
+    /// The binary exponent.
+    exponent: i32,
+
+    /// Whether any (non-zero) bits were dropped, because they didn't fit in `bits`.
+    sticky: bool,
+}
+
+impl Significand {
+    /// Appends hexadecimal digits (and separators), either before or after the point.
+    fn push_digits(&mut self, digits: &[u8], fractional: bool) {
+        for &b in digits.iter().filter(|&&b| b != b'_') {
+            let digit = (b as char).to_digit(16).expect("a hexadecimal digit") as u64;
+            if self.bits >> 60 == 0 {
+                self.bits = self.bits << 4 | digit;
+                if fractional {
+                    self.exponent -= 4;
+                }
+            } else {
+                self.sticky |= digit != 0;
+                if !fractional {
+                    self.exponent += 4;
+                }
+            }
+        }
+    }
+
+    /// Converts the significand, rounding to the nearest value (ties to even).
+    fn value<T: Float>(&self) -> T {
+        if self.bits == 0 {
+            return T::ZERO;
+        }
+
+        // Normalize, so that the top bit is set, and fold the dropped bits in below the rounding
+        // bit, so that they break ties.
+        let shift = self.bits.leading_zeros();
+        let bits = self.bits << shift | u64::from(self.sticky);
+        let exponent = self.exponent.saturating_sub(shift as i32);
+
+        // The number of bits the type has at this magnitude (less than the usual amount when the
+        // value is subnormal).
+        let top = exponent.saturating_add(63);
+        let lowest = T::MIN_EXP - T::MANTISSA_DIGITS as i32;
+        let precision = match top {
+            top if top >= T::MIN_EXP - 1 => T::MANTISSA_DIGITS as i32,
+            top => top - lowest + 1,
+        };
+
+        match precision {
+            // Rounds up to the smallest subnormal, unless it's exactly half of it (a tie to zero)
+            0 if bits > 1 << 63 => scale(T::from_u64(1), lowest),
+            ..=0 => T::ZERO,
+            precision => {
+                let dropped = 64 - precision as u32;
+                let mut rounded = bits >> dropped;
+                let rest = bits & ((1 << dropped) - 1);
+                let half = 1 << (dropped - 1);
+                if rest > half || (rest == half && rounded & 1 == 1) {
+                    rounded += 1;
+                }
+
+                scale(
+                    T::from_u64(rounded),
+                    exponent.saturating_add(dropped as i32),
+                )
+            }
+        }
+    }
+}
+
+/// INTERNAL: Multiplies `x` by `2^exp`, in steps so that the powers of two don't overflow. Exact,
+/// as long as the result is representable.
+fn scale<T: Float>(mut x: T, mut exp: i32) -> T {
+    let (max, min) = (T::MAX_EXP - 1, T::MIN_EXP - 1);
+
+    while exp > max {
+        x = x * T::pow2(max);
+        exp -= max;
+        if exp > 4 * max {
+            return T::INFINITY;
+        }
+    }
+
+    while exp < min {
+        x = x * T::pow2(min);
+        exp -= min;
+        if exp < 4 * min {
+            return T::ZERO;
+        }
+    }
+
+    x * T::pow2(exp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::errors::DummyError;
+
+    fn parse<T: Float>(input: &str, format: FloatFormat) -> Option<T> {
+        float::<T, _, DummyError, ()>()
+            .format(format)
+            .parse(input)
+            .ok()
+    }
+
+    fn rest(input: &str, format: FloatFormat) -> &str {
+        let parser = float::<f64, _, DummyError, ()>().format(format);
+        match parser.process(input.into()) {
+            Ok((state, _)) => state.as_input().as_inner(),
+            Err(state) => state.as_input().as_inner(),
+        }
+    }
+
+    #[test]
+    fn can_parse_floats() {
+        let any = FloatFormat::default();
+        assert_eq!(parse::<f64>("1.5", any), Some(1.5));
+        assert_eq!(parse::<f64>("-0.25e2", any), Some(-25.0));
+        assert_eq!(parse::<f64>("+1E-2", any), Some(0.01));
+        assert_eq!(parse::<f64>(".5", any), Some(0.5));
+        assert_eq!(parse::<f64>("5.", any), Some(5.0));
+        assert_eq!(parse::<f64>("42", any), Some(42.0));
+        assert_eq!(parse::<f64>("1_000.000_1", any), Some(1000.0001));
+        assert_eq!(parse::<f64>("1e400", any), Some(f64::INFINITY));
+        assert_eq!(parse::<f32>("0.1", any), Some(0.1));
+        assert_eq!(parse::<f64>("-INF", any), Some(f64::NEG_INFINITY));
+        assert_eq!(parse::<f64>("Infinity", any), Some(f64::INFINITY));
+        assert!(parse::<f64>("NaN", any).is_some_and(f64::is_nan));
+        assert!(parse::<f64>("-0.0", any).is_some_and(|f| f == 0.0 && f.is_sign_negative()));
+
+        // Correctly rounded, unlike summing up the digits
+        assert_eq!(parse::<f64>("0.30000000000000004", any), Some(0.1 + 0.2));
+        assert_eq!(
+            parse::<f64>("2.2250738585072011e-308", any),
+            Some(2.225_073_858_507_201e-308)
+        );
+
+        // Only as much as makes a float
+        assert_eq!(rest("1.5.2", any), ".2");
+        assert_eq!(rest("1e", any), "e");
+        assert_eq!(rest("1e+x", any), "e+x");
+        assert_eq!(rest("1..2", any), "..2");
+        assert_eq!(rest("1.max(2)", any), ".max(2)");
+        assert_eq!(rest("1.e3;", any), ";");
+        assert_eq!(rest("0x1;", any), "x1;");
+    }
+
+    #[test]
+    fn can_parse_hex_floats() {
+        let any = FloatFormat::default();
+        assert_eq!(parse::<f64>("0x1.8p1", any), Some(3.0));
+        assert_eq!(parse::<f64>("-0X.8P-1", any), Some(-0.25));
+        assert_eq!(parse::<f64>("0x10p0", any), Some(16.0));
+        assert_eq!(parse::<f64>("0x1.fffffffffffffp1023", any), Some(f64::MAX));
+        assert_eq!(parse::<f64>("0x1p1024", any), Some(f64::INFINITY));
+        assert_eq!(parse::<f64>("0x1p-1022", any), Some(f64::MIN_POSITIVE));
+        assert_eq!(parse::<f64>("0x0p99999999999", any), Some(0.0));
+        assert_eq!(parse::<f32>("0x1.fffffep127", any), Some(f32::MAX));
+        assert_eq!(parse::<f32>("0x1p-149", any), Some(f32::from_bits(1)));
+
+        // Rounds to the nearest, with ties to even
+        let one = 1.0f64;
+        let next = f64::from_bits(one.to_bits() + 1);
+        let next2 = f64::from_bits(one.to_bits() + 2);
+        assert_eq!(parse::<f64>("0x1.00000000000008p0", any), Some(one));
+        assert_eq!(
+            parse::<f64>("0x1.00000000000008000000001p0", any),
+            Some(next)
+        );
+        assert_eq!(parse::<f64>("0x1.00000000000018p0", any), Some(next2));
+        assert_eq!(parse::<f64>("0x1.0000000000000fffffp0", any), Some(next));
+        assert_eq!(parse::<f32>("0x1.000001p0", any), Some(1.0));
+        assert_eq!(
+            parse::<f32>("0x1.000003p0", any),
+            Some(1.0 + f32::EPSILON * 2.0)
+        );
+
+        // Including when the result is subnormal
+        let min = f64::from_bits(1);
+        assert_eq!(parse::<f64>("0x1p-1074", any), Some(min));
+        assert_eq!(parse::<f64>("0x1p-1075", any), Some(0.0));
+        assert_eq!(parse::<f64>("0x1.0000001p-1075", any), Some(min));
+        assert_eq!(parse::<f64>("0x1.8p-1074", any), Some(f64::from_bits(2)));
+        assert_eq!(parse::<f64>("0x1.4p-1073", any), Some(f64::from_bits(2)));
+        assert_eq!(parse::<f64>("0x1p-1076", any), Some(0.0));
+        assert_eq!(
+            parse::<f64>("0x0.fffffffffffff8p-1022", any),
+            Some(f64::MIN_POSITIVE)
+        );
+
+        // And matches the bits of any value
+        for bits in (0..u64::MAX >> 1).step_by(0x0000_7919_3b1d_0c0d) {
+            let f = f64::from_bits(bits);
+            if !f.is_finite() {
+                continue;
+            }
+
+            let exponent = (bits >> 52) as i32;
+            let mantissa = bits & ((1 << 52) - 1);
+            let literal = match exponent {
+                0 => format!("0x0.{:013x}p-1022", mantissa),
+                exponent => format!("0x1.{:013x}p{}", mantissa, exponent - 1023),
+            };
+            assert_eq!(parse::<f64>(&literal, any), Some(f), "{}", literal);
+        }
+    }
+
+    #[test]
+    fn formats_restrict_grammar() {
+        let json = FloatFormat::json();
+        assert_eq!(parse::<f64>("-1.5e3", json), Some(-1500.0));
+        assert_eq!(parse::<f64>("10", json), Some(10.0));
+        for input in [".5", "+1", "inf", "NaN"] {
+            assert_eq!(parse::<f64>(input, json), None, "{:?}", input);
+        }
+        assert_eq!(rest("5.", json), ".");
+        assert_eq!(rest("01", json), "1");
+        assert_eq!(rest("1_0", json), "_0");
+        assert_eq!(rest("0x1p0", json), "x1p0");
+
+        let rust = FloatFormat::rust();
+        assert_eq!(parse::<f64>("1_000.", rust), Some(1000.0));
+        assert_eq!(parse::<f64>("1e1_0", rust), Some(1e10));
+        assert_eq!(parse::<f64>("1", rust), None);
+        assert_eq!(parse::<f64>(".5", rust), None);
+
+        let c = FloatFormat::c();
+        assert_eq!(parse::<f64>(".5", c), Some(0.5));
+        assert_eq!(parse::<f64>("0x1p-3", c), Some(0.125));
+        assert_eq!(parse::<f64>("1", c), None);
+        assert_eq!(rest("1_0.0", c), "1_0.0");
+    }
+
+    #[test]
+    fn floats_report_errors() {
+        for (input, span, format) in [
+            ("x", 0..1, FloatFormat::default()),
+            ("-.", 1..2, FloatFormat::default()),
+            ("", 0..0, FloatFormat::default()),
+            ("12;", 0..2, FloatFormat::rust()),
+            ("-12;", 0..3, FloatFormat::c()),
+        ] {
+            let state: State<&str> = float::<f64, _, _, ()>()
+                .format(format)
+                .process(input.into())
+                .unwrap_err();
+            assert_eq!(state.as_input(), &input);
+            assert_eq!(
+                state.errors(),
+                &Error::new(
+                    ErrorKind::expected(ExpectedError::Float),
+                    Input::new_with_span(input, span)
+                ),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn can_recognize_floats() {
+        let (state, parsed): (State<&str>, Input<&str>) = float::<f32, _, _, ()>()
+            .recognize()
+            .process("-1_0.5e+3,".into())
+            .unwrap();
+        assert_eq!(parsed, "-1_0.5e+3");
+        assert_eq!(state.as_input(), &",");
+    }
+}
@@ -0,0 +1,61 @@
+//! INTERNAL: Building blocks for parsers that match glyphs (bytes, or `char`s with the `unicode`
+//! feature) one at a time. See `decode_glyph` for what a glyph is.
+
+use crate::parser::{
+    errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
+    input::{decode_glyph, Input, Underlying},
+    state::State,
+};
+
+/// Parses a single glyph that matches `f`. If it doesn't match, errors with `expected` over the
+/// offending glyph.
+#[inline]
+pub(crate) fn glyph<I: Underlying, E: CustomError, C, F: Fn(char) -> bool>(
+    state: State<I, E, C>,
+    f: F,
+    expected: ExpectedError<I>,
+) -> Result<I, Input<I>, E, C> {
+    let input = state.as_input().fork();
+    match input.peek_glyph() {
+        Some((c, len)) if f(c) => Ok((state.with_input(input.skip(len)), input.take(len))),
+        Some((_, len)) => {
+            Err(state.with_error(Error::new(ErrorKind::expected(expected), input.take(len))))
+        }
+        None => Err(state.with_error(Error::new(ErrorKind::expected(expected), input.take(1)))),
+    }
+}
+
+/// Parses as many glyphs that match `f` as possible, but at least one. If the first glyph doesn't
+/// match, errors the same way `glyph` does.
+#[inline]
+pub(crate) fn glyphs<I: Underlying, E: CustomError, C, F: Fn(char) -> bool>(
+    state: State<I, E, C>,
+    f: F,
+    expected: ExpectedError<I>,
+) -> Result<I, Input<I>, E, C> {
+    let input = state.as_input().fork();
+    let len = glyphs_len(input.as_bytes(), f);
+
+    if len == 0 {
+        return glyph(state, |_| false, expected);
+    }
+
+    Ok((state.with_input(input.skip(len)), input.take(len)))
+}
+
+/// Gets the length (in bytes) of the longest run of glyphs at the start of `bytes` that all match
+/// `f`.
+#[inline]
+pub(crate) fn glyphs_len<F: Fn(char) -> bool>(bytes: &[u8], f: F) -> usize {
+    let mut len = 0;
+
+    while let Some((c, n)) = decode_glyph(&bytes[len..]) {
+        if !f(c) {
+            break;
+        }
+
+        len += n;
+    }
+
+    len
+}
@@ -14,7 +14,7 @@ use crate::parser::{
 /// assert_eq!(parsed, "test");
 /// assert_eq!(state.as_input().as_inner(), "");
 /// ```
-pub fn id<I: Underlying, E: CustomError>(state: State<I, E>) -> Result<I, Input<I>, E> {
+pub fn id<I: Underlying, E: CustomError, C>(state: State<I, E, C>) -> Result<I, Input<I>, E, C> {
     let input = state.as_input().fork();
     Ok((state.with_input(input.skip_all()), input))
 }
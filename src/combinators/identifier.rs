@@ -0,0 +1,278 @@
+use super::glyph::{glyph, glyphs_len};
+use crate::parser::{
+    errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
+    input::{decode_glyph, Input, Underlying},
+    state::State,
+    Parser,
+};
+
+/// Parses an ASCII identifier: an ASCII letter, followed by any number of ASCII letters or
+/// digits. Use the builder methods on `Identifier` to allow extra characters (like `_` or `$`) or
+/// to reserve keywords.
+///
+/// ```
+/// # use errgonomic::combinators::identifier;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = identifier()
+///     .with_start_chars("_")
+///     .process("_foo1 = 2".into())
+///     .unwrap();
+/// assert_eq!(parsed, "_foo1");
+/// assert_eq!(state.as_input().as_inner(), " = 2");
+/// ```
+pub fn identifier<I: Underlying, E: CustomError, C>() -> Identifier<I, E, C> {
+    Identifier {
+        #[cfg(feature = "unicode")]
+        unicode: false,
+        start_chars: vec![],
+        continue_chars: vec![],
+        keywords: vec![],
+        _marker: std::marker::PhantomData,
+    }
+}
+
+/// Parses a Unicode identifier, as defined by UAX #31: a character with the `XID_Start`
+/// property, followed by any number of characters with the `XID_Continue` property. Use the
+/// builder methods on `Identifier` to allow extra characters (like `_` or `$`) or to reserve
+/// keywords.
+///
+/// See: https://www.unicode.org/reports/tr31/
+///
+/// NOTE: Only available with the `unicode` feature enabled.
+///
+/// ```
+/// # use errgonomic::combinators::unicode_identifier;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = unicode_identifier()
+///     .process("переменная_1 + 2".into())
+///     .unwrap();
+/// assert_eq!(parsed, "переменная_1");
+/// assert_eq!(state.as_input().as_inner(), " + 2");
+/// ```
+#[cfg(feature = "unicode")]
+pub fn unicode_identifier<I: Underlying, E: CustomError, C>() -> Identifier<I, E, C> {
+    Identifier {
+        unicode: true,
+        ..identifier()
+    }
+}
+
+/// An identifier parser. See `identifier` and `unicode_identifier`.
+#[derive(Debug, Clone)]
+pub struct Identifier<I: Underlying, E: CustomError, C = ()> {
+    /// Whether to use UAX #31 (`XID_Start`/`XID_Continue`) instead of ASCII.
+    #[cfg(feature = "unicode")]
+    unicode: bool,
+
+    /// Extra characters that can start (and continue) an identifier.
+    start_chars: Vec<char>,
+
+    /// Extra characters that can continue an identifier.
+    continue_chars: Vec<char>,
+
+    /// Reserved keywords, which are not identifiers.
+    keywords: Vec<I>,
+
+    _marker: std::marker::PhantomData<(E, C)>,
+}
+
+impl<I: Underlying, E: CustomError, C> Identifier<I, E, C> {
+    /// Allows the identifier to start with any of `chars`. As any character that can start an
+    /// identifier can also continue one, they are also allowed after the start.
+    ///
+    /// ```
+    /// # use errgonomic::combinators::identifier;
+    /// # use errgonomic::parser::Parser;
+    /// # use errgonomic::parser::errors::DummyError;
+    /// let parsed = identifier::<_, DummyError, ()>().with_start_chars("_$").parse("$_a$").unwrap();
+    /// assert_eq!(parsed, "$_a$");
+    /// ```
+    pub fn with_start_chars(mut self, chars: &str) -> Self {
+        self.start_chars.extend(chars.chars());
+        self
+    }
+
+    /// Allows the identifier to contain any of `chars`, but not to start with them.
+    ///
+    /// ```
+    /// # use errgonomic::combinators::identifier;
+    /// # use errgonomic::parser::Parser;
+    /// # use errgonomic::parser::errors::DummyError;
+    /// let mut parser = identifier::<_, DummyError, ()>().with_continue_chars("-");
+    /// assert_eq!(parser.parse("kebab-case").unwrap(), "kebab-case");
+    /// assert!(parser.parse("-kebab").is_err());
+    /// ```
+    pub fn with_continue_chars(mut self, chars: &str) -> Self {
+        self.continue_chars.extend(chars.chars());
+        self
+    }
+
+    /// Reserves `keywords`, so that they are not parsed as identifiers. Instead, a
+    /// `NotKeyword` error is returned.
+    ///
+    /// NOTE: Only whole identifiers are checked, so `fnord` is still an identifier even if `fn` is
+    /// reserved.
+    ///
+    /// ```
+    /// # use errgonomic::combinators::identifier;
+    /// # use errgonomic::parser::Parser;
+    /// # use errgonomic::parser::errors::DummyError;
+    /// let err = identifier::<_, DummyError, ()>().with_keywords(["fn", "let"]).parse("fn").unwrap_err();
+    /// assert_eq!(err.to_string(), "`fn` is a reserved keyword");
+    /// ```
+    pub fn with_keywords<K: IntoIterator<Item = I>>(mut self, keywords: K) -> Self {
+        self.keywords.extend(keywords);
+        self
+    }
+
+    /// Whether `c` can start an identifier.
+    fn is_start(&self, c: char) -> bool {
+        #[cfg(feature = "unicode")]
+        if self.unicode && unicode_ident::is_xid_start(c) {
+            return true;
+        }
+
+        c.is_ascii_alphabetic() || self.start_chars.contains(&c)
+    }
+
+    /// Whether `c` can continue an identifier.
+    fn is_continue(&self, c: char) -> bool {
+        #[cfg(feature = "unicode")]
+        if self.unicode && unicode_ident::is_xid_continue(c) {
+            return true;
+        }
+
+        c.is_ascii_alphanumeric()
+            || self.start_chars.contains(&c)
+            || self.continue_chars.contains(&c)
+    }
+}
+
+impl<I: Underlying, E: CustomError, C> Parser<I, Input<I>, E, C> for Identifier<I, E, C> {
+    fn process(&self, state: State<I, E, C>) -> Result<I, Input<I>, E, C> {
+        let input = state.as_input().fork();
+        let bytes = input.as_bytes();
+
+        let len = match decode_glyph(bytes) {
+            Some((c, n)) if self.is_start(c) => {
+                n + glyphs_len(&bytes[n..], |c| self.is_continue(c))
+            }
+            _ => return glyph(state, |_| false, ExpectedError::Identifier),
+        };
+
+        let ident = input.take(len);
+        if self.keywords.iter().any(|k| ident == *k) {
+            return Err(state.with_error(Error::new(
+                ErrorKind::expected(ExpectedError::NotKeyword(ident.as_inner())),
+                ident,
+            )));
+        }
+
+        Ok((state.with_input(input.skip(len)), ident))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::errors::DummyError;
+
+    #[test]
+    fn can_parse_identifier() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            identifier().process("abc123 def".into()).unwrap();
+        assert_eq!(parsed, "abc123");
+        assert_eq!(state.as_input(), &" def");
+        assert!(!state.is_err());
+
+        let state: State<&str> = identifier().process("1abc".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Identifier),
+                Input::new_with_span("1abc", 0..1)
+            )
+        );
+
+        // Underscores aren't allowed unless asked for
+        assert!(identifier::<_, DummyError, ()>().parse("_a").is_err());
+    }
+
+    #[test]
+    fn can_parse_identifier_with_extra_chars() {
+        let parser = identifier().with_start_chars("_$").with_continue_chars("'");
+
+        let (state, parsed): (State<&str>, Input<&str>) =
+            parser.process("$x_1'' = 3".into()).unwrap();
+        assert_eq!(parsed, "$x_1''");
+        assert_eq!(state.as_input(), &" = 3");
+
+        let (_, parsed): (State<&str>, Input<&str>) = parser.process("__".into()).unwrap();
+        assert_eq!(parsed, "__");
+
+        let state: State<&str> = parser.process("'x".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Identifier),
+                Input::new_with_span("'x", 0..1)
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_keywords() {
+        let parser = identifier()
+            .with_start_chars("_")
+            .with_keywords(["fn", "let"]);
+
+        let state: State<&str> = parser.process("fn main".into()).unwrap_err();
+        assert_eq!(state.as_input(), &"fn main");
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::NotKeyword("fn")),
+                Input::new_with_span("fn main", 0..2)
+            )
+        );
+        assert_eq!(state.errors().to_string(), "`fn` is a reserved keyword");
+
+        // Only whole identifiers are keywords
+        let (_, parsed): (State<&str>, Input<&str>) = parser.process("fnord".into()).unwrap();
+        assert_eq!(parsed, "fnord");
+        let (_, parsed): (State<&str>, Input<&str>) = parser.process("let_".into()).unwrap();
+        assert_eq!(parsed, "let_");
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn can_parse_unicode_identifier() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            unicode_identifier().process("変数x١ = 1".into()).unwrap();
+        assert_eq!(parsed, "変数x١");
+        assert_eq!(state.as_input(), &" = 1");
+
+        // `XID_Continue`, but not `XID_Start`
+        let state: State<&str> = unicode_identifier().process("١x".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Identifier),
+                Input::new_with_span("١x", 0..2)
+            )
+        );
+
+        // Combining marks can continue an identifier, but emoji can't
+        let (state, parsed): (State<&str>, Input<&str>) =
+            unicode_identifier().process("e\u{0301}😊".into()).unwrap();
+        assert_eq!(parsed, "e\u{0301}");
+        assert_eq!(state.as_input(), &"😊");
+
+        // ASCII mode doesn't parse Unicode identifiers
+        assert!(identifier::<_, DummyError, ()>().parse("変数").is_err());
+    }
+}
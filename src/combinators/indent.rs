@@ -0,0 +1,362 @@
+use super::whitespace::newline_len;
+use crate::parser::{
+    errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
+    input::{decode_glyph, Input, Underlying},
+    state::State,
+    Parser,
+};
+
+/// How the indentation of a line compares to the current indentation level. See `indentation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indentation {
+    /// The line is indented further, i.e. an `INDENT` token.
+    Indent,
+
+    /// The line is at the current indentation level.
+    Same,
+
+    /// The line closes this many indented blocks, i.e. this many `DEDENT` tokens.
+    Dedent(usize),
+}
+
+/// Parses the newline(s) ending the current line, any blank lines, and then the leading
+/// whitespace of the next line, and compares its indentation to the current indentation level
+/// (see `State::indent`). The end of the input closes all the indented blocks.
+///
+/// Spaces count as one column, and tabs advance to the next multiple of the tab width (see
+/// `State::with_tab_width`).
+///
+/// If the line is indented less, it has to line up with one of the enclosing blocks, or a
+/// committed `Unindent` error is returned. If there is no newline, a `Newlines` error is returned.
+///
+/// NOTE: This only detects indentation, and doesn't enter or leave any blocks (see
+/// `indented_block`).
+///
+/// ```
+/// # use errgonomic::combinators::{indentation, Indentation};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// let (state, parsed): (State<&str>, Indentation) = indentation.process("\n\n  \tx".into()).unwrap();
+/// assert_eq!(parsed, Indentation::Indent);
+/// assert_eq!(state.as_input().as_inner(), "x");
+///
+/// let (_, parsed): (State<&str>, Indentation) = indentation.process("\nx".into()).unwrap();
+/// assert_eq!(parsed, Indentation::Same);
+/// ```
+pub fn indentation<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Indentation, E, C> {
+    let input = state.as_input().fork();
+    let Some(line) = next_line(input.as_bytes(), state.tab_width()) else {
+        return Err(state.with_error(newline_error(&input)));
+    };
+
+    let indents = state.indents();
+    let indentation = match line.width {
+        _ if line.eof => Indentation::Dedent(indents.len()),
+        width if width > state.indent() => Indentation::Indent,
+        width if width == state.indent() => Indentation::Same,
+        width => {
+            let closed = indents.iter().rev().take_while(|&&i| i > width).count();
+            let outer = indents.len() - closed;
+            if outer.checked_sub(1).map_or(0, |i| indents[i]) != width {
+                let error = line.error(&input, ExpectedError::Unindent).commit();
+                return Err(state.with_error(error));
+            }
+
+            Indentation::Dedent(closed)
+        }
+    };
+
+    Ok((state.with_input(input.skip(line.end)), indentation))
+}
+
+/// Parses the start of the next line (see `indentation`), which has to be at the current
+/// indentation level, i.e. the next statement of a block.
+///
+/// If it's indented further, a committed `UnexpectedIndent` error is returned. If it's indented
+/// less (or it's the end of the input), an uncommitted `SameIndent` error is returned, as that's
+/// just the end of the block.
+///
+/// ```
+/// # use errgonomic::combinators::same_indent;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// let (state, _): (State<&str>, ()) = same_indent.process("\nx".into()).unwrap();
+/// assert_eq!(state.as_input().as_inner(), "x");
+///
+/// let state: State<&str> = same_indent.process("\n  x".into()).unwrap_err();
+/// assert_eq!(state.errors().to_string(), "unexpected indent");
+/// ```
+pub fn same_indent<I: Underlying, E: CustomError, C>(state: State<I, E, C>) -> Result<I, (), E, C> {
+    let input = state.as_input().fork();
+    let line = next_line(input.as_bytes(), state.tab_width());
+
+    match indentation.process(state.fork()) {
+        Ok((state, Indentation::Same)) => Ok((state, ())),
+        Ok((_, indentation)) => {
+            let (expected, committed) = match indentation {
+                Indentation::Indent => (ExpectedError::UnexpectedIndent, true),
+                _ => (ExpectedError::SameIndent, false),
+            };
+            let line = line.expect("a line, as `indentation` found one");
+            let error = line.error(&input, expected);
+            Err(state.with_error(if committed { error.commit() } else { error }))
+        }
+        Err(state) => Err(state),
+    }
+}
+
+/// Parses an indented block, i.e. a newline followed by one or more lines of `p` indented further
+/// than the current level, each at the same indentation. The block ends at the first line that is
+/// indented less (which isn't consumed), and `p` can parse nested blocks in turn.
+///
+/// If the first line isn't indented further, an `Indent` error is returned. Any line indented
+/// further than the block (but not in a nested block), or that doesn't line up with an enclosing
+/// block, is a committed error (see `same_indent` and `indentation`).
+///
+/// ```
+/// # use errgonomic::combinators::{indented_block, alphabetic, is};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// let statement = alphabetic;
+/// let parser = is("if:").then(indented_block(statement));
+///
+/// let (state, (_, block)): (State<&str>, (Input<&str>, Vec<Input<&str>>)) =
+///     parser.process("if:\n  a\n\n  b\nc".into()).unwrap();
+/// assert_eq!(block, ["a", "b"]);
+/// assert_eq!(state.as_input().as_inner(), "\nc");
+///
+/// let state: State<&str> = parser.process("if:\n    a\n  b".into()).unwrap_err();
+/// assert_eq!(
+///     state.errors().to_string(),
+///     "unindent does not match any outer indentation level"
+/// );
+/// ```
+pub fn indented_block<I: Underlying, O, E: CustomError, C, P: Parser<I, O, E, C>>(
+    p: P,
+) -> impl Parser<I, Vec<O>, E, C> {
+    move |state: State<I, E, C>| {
+        let input = state.as_input().fork();
+        let Some(line) = next_line(input.as_bytes(), state.tab_width()) else {
+            return Err(state.with_error(newline_error(&input)));
+        };
+
+        if line.eof || line.width <= state.indent() {
+            return Err(state.with_error(line.error(&input, ExpectedError::Indent)));
+        }
+
+        let mut state = state
+            .with_input(input.skip(line.end))
+            .push_indent(line.width);
+        let mut outputs = vec![];
+
+        loop {
+            match p.process(state) {
+                Ok((new_state, o)) => {
+                    state = new_state;
+                    outputs.push(o);
+                }
+                Err(state) => return Err(state.pop_indent()),
+            }
+
+            match same_indent.process(state.fork()) {
+                Ok((new_state, ())) => state = new_state,
+                Err(state) if state.errors().is_committed() => return Err(state.pop_indent()),
+                Err(_) => break,
+            }
+        }
+
+        Ok((state.pop_indent(), outputs))
+    }
+}
+
+/// INTERNAL: The start of the next (non-blank) line.
+struct Line {
+    /// Where the line starts, after the newline(s).
+    start: usize,
+
+    /// Where the leading whitespace of the line ends.
+    end: usize,
+
+    /// The width of the leading whitespace, in columns.
+    width: usize,
+
+    /// Whether there is no next line, i.e. the input ends.
+    eof: bool,
+}
+
+impl Line {
+    /// Gets an error over the leading whitespace of the line.
+    fn error<I: Underlying, E: CustomError>(
+        &self,
+        input: &Input<I>,
+        expected: ExpectedError<I>,
+    ) -> Error<I, E> {
+        Error::new(
+            ErrorKind::expected(expected),
+            input.skip(self.start).take(self.end - self.start),
+        )
+    }
+}
+
+/// INTERNAL: Finds the start of the next non-blank line, after at least one newline.
+fn next_line(bytes: &[u8], tab_width: usize) -> Option<Line> {
+    let mut start = newline_len(bytes)?;
+
+    loop {
+        let mut end = start;
+        let mut width = 0;
+        while let Some(&b) = bytes.get(end) {
+            width = match b {
+                b' ' => width + 1,
+                b'\t' => (width / tab_width + 1) * tab_width,
+                _ => break,
+            };
+            end += 1;
+        }
+
+        match newline_len(&bytes[end..]) {
+            Some(n) => start = end + n,
+            None => {
+                return Some(Line {
+                    start,
+                    end,
+                    width,
+                    eof: end == bytes.len(),
+                })
+            }
+        }
+    }
+}
+
+/// INTERNAL: The error for when there is no newline, over the next glyph.
+fn newline_error<I: Underlying, E: CustomError>(input: &Input<I>) -> Error<I, E> {
+    let len = decode_glyph(input.as_bytes()).map_or(0, |(_, len)| len);
+    Error::new(
+        ErrorKind::expected(ExpectedError::Newlines),
+        input.take(len),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        combinators::{alphabetic, is, optional},
+        parser::errors::DummyError,
+    };
+
+    #[derive(Debug, PartialEq)]
+    enum Statement<'a> {
+        Simple(&'a str),
+        Block(&'a str, Vec<Statement<'a>>),
+    }
+
+    fn statement<'a>(state: State<&'a str>) -> Result<&'a str, Statement<'a>, DummyError> {
+        alphabetic
+            .then(optional(is(":").then(indented_block(statement))))
+            .map(|(name, block)| match block {
+                Some((_, block)) => Statement::Block(name.as_inner(), block),
+                None => Statement::Simple(name.as_inner()),
+            })
+            .process(state)
+    }
+
+    #[test]
+    fn can_parse_nested_blocks() {
+        let input = "if:\n  a\n  while:\n\n      b\n      c\n  d\ne";
+        let (state, parsed) = statement.process(input.into()).unwrap();
+        assert_eq!(
+            parsed,
+            Statement::Block(
+                "if",
+                vec![
+                    Statement::Simple("a"),
+                    Statement::Block(
+                        "while",
+                        vec![Statement::Simple("b"), Statement::Simple("c")]
+                    ),
+                    Statement::Simple("d"),
+                ]
+            )
+        );
+        assert_eq!(state.as_input(), &"\ne");
+        assert!(state.indents().is_empty());
+
+        // Closing multiple blocks at once, and at the end of the input
+        let (state, parsed) = statement.process("a:\n b:\n  c\nd".into()).unwrap();
+        assert_eq!(
+            parsed,
+            Statement::Block(
+                "a",
+                vec![Statement::Block("b", vec![Statement::Simple("c")])]
+            )
+        );
+        assert_eq!(state.as_input(), &"\nd");
+
+        let (state, _) = statement.process("a:\n b:\n  c\n\n".into()).unwrap();
+        assert_eq!(state.as_input(), &"\n\n");
+    }
+
+    #[test]
+    fn indented_blocks_report_errors() {
+        for (input, expected, span, committed) in [
+            ("a:\nb", ExpectedError::Indent, 3..3, false),
+            ("a:\n", ExpectedError::Indent, 3..3, false),
+            ("a: b", ExpectedError::Newlines, 2..3, false),
+            (
+                "a:\n  b\n    c",
+                ExpectedError::UnexpectedIndent,
+                7..11,
+                true,
+            ),
+            ("a:\n  b:\n    c\n d", ExpectedError::Unindent, 14..15, true),
+        ] {
+            let state = is("a:")
+                .then(indented_block(statement))
+                .process(input.into())
+                .unwrap_err();
+            let error = Error::new(
+                ErrorKind::expected(expected),
+                Input::new_with_span(input, span),
+            );
+            let error = if committed { error.commit() } else { error };
+            assert_eq!(state.errors(), &error, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn can_detect_indentation() {
+        let state = State::<&str>::new("\n  x").push_indent(2).push_indent(8);
+        let (state, parsed) = indentation.process(state).unwrap();
+        assert_eq!(parsed, Indentation::Dedent(1));
+        assert_eq!(state.as_input(), &"x");
+
+        // Tabs go to the next multiple of the tab width
+        let state = State::<&str>::new("\n\t\ty\n").push_indent(8);
+        let (state, parsed) = indentation.process(state).unwrap();
+        assert_eq!(parsed, Indentation::Indent);
+        assert_eq!(state.as_input(), &"y\n");
+
+        let state = State::<&str>::new("\n \ty")
+            .with_tab_width(4)
+            .push_indent(4);
+        let (_, parsed) = indentation.process(state).unwrap();
+        assert_eq!(parsed, Indentation::Same);
+
+        // The end of the input closes every block
+        let state = State::<&str>::new("\n\n  ").push_indent(2).push_indent(4);
+        let (state, parsed) = indentation.process(state).unwrap();
+        assert_eq!(parsed, Indentation::Dedent(2));
+        assert!(state.as_input().is_empty());
+
+        let state = State::<&str>::new("\n   x").push_indent(2).push_indent(4);
+        let state = indentation.process(state).unwrap_err();
+        assert_eq!(
+            state.errors().to_string(),
+            "unindent does not match any outer indentation level"
+        );
+    }
+}
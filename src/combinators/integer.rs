@@ -0,0 +1,268 @@
+use crate::parser::{
+    errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
+    input::{decode_glyph, Underlying},
+    state::State,
+    Parser,
+};
+use eval_macro::eval;
+
+/// A primitive integer type that `integer` and `integer_radix` can parse into.
+pub trait Integer: Copy {
+    /// Whether the type can be negative.
+    const SIGNED: bool;
+
+    /// Zero, which parsing starts from.
+    const ZERO: Self;
+
+    /// Appends a digit in the given radix (i.e. `self * radix + digit`, or `self * radix - digit`
+    /// if the number is negative). Returns `None` if the result doesn't fit.
+    fn push_digit(self, digit: u32, radix: u32, negative: bool) -> Option<Self>;
+}
+
+// Magic macro magic that makes the impl. of `Integer` for all the primitive integers.
+// See: https://crates.io/crates/eval-macro
+eval! {
+    let types = [
+        ("i8", true), ("i16", true), ("i32", true), ("i64", true), ("i128", true), ("isize", true),
+        ("u8", false), ("u16", false), ("u32", false), ("u64", false), ("u128", false), ("usize", false),
+    ];
+
+    for (ty, signed) in types {
+        output! {
+            impl Integer for {{ty}} {
+                const SIGNED: bool = {{signed}};
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn push_digit(self, digit: u32, radix: u32, negative: bool) -> Option<Self> {
+                    // NOTE: Radixes are at most 36, so they (and their digits) fit in any type.
+                    let shifted = self.checked_mul(radix as {{ty}})?;
+                    if negative {
+                        shifted.checked_sub(digit as {{ty}})
+                    } else {
+                        shifted.checked_add(digit as {{ty}})
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Parses an integer literal into any primitive integer type. The literal may start with a `+`
+/// (or `-`, for signed types), followed by a radix prefix (`0x`, `0o` or `0b`, in any case), and
+/// its digits may be separated by `_` (but can't start with one).
+///
+/// If the literal doesn't fit in the type, an `InRange` error is returned over the whole literal
+/// (and nothing is consumed), instead of it overflowing or panicking.
+///
+/// ```
+/// # use errgonomic::combinators::integer;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::errors::DummyError;
+/// let (state, parsed): (State<&str>, i32) = integer::<i32, _, _, ()>().process("-1_000;".into()).unwrap();
+/// assert_eq!(parsed, -1000);
+/// assert_eq!(state.as_input().as_inner(), ";");
+///
+/// assert_eq!(integer::<u8, _, DummyError, ()>().parse("0xff"), Ok(255));
+///
+/// let state: State<&str> = integer::<u8, _, _, ()>().process("256".into()).unwrap_err();
+/// assert_eq!(state.errors().to_string(), "expected a number that fits in `u8`");
+/// ```
+pub fn integer<T: Integer, I: Underlying, E: CustomError, C>() -> impl Parser<I, T, E, C> {
+    move |state| parse_integer(state, None)
+}
+
+/// Like `integer`, but always in the given radix, without a radix prefix.
+///
+/// NOTE: Panics if `radix` isn't between 2 and 36 (inclusive).
+///
+/// ```
+/// # use errgonomic::combinators::integer_radix;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::errors::DummyError;
+/// assert_eq!(integer_radix::<u32, _, DummyError, ()>(16).parse("dead_beef"), Ok(0xdead_beef));
+/// assert_eq!(integer_radix::<i8, _, DummyError, ()>(2).parse("-1000_0000"), Ok(-128));
+/// ```
+pub fn integer_radix<T: Integer, I: Underlying, E: CustomError, C>(
+    radix: u32,
+) -> impl Parser<I, T, E, C> {
+    assert!(
+        (2..=36).contains(&radix),
+        "The radix must be between 2 and 36!"
+    );
+
+    move |state| parse_integer(state, Some(radix))
+}
+
+/// INTERNAL: Parses an integer, in the given radix, or the radix of its prefix if there is none.
+fn parse_integer<T: Integer, I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+    radix: Option<u32>,
+) -> Result<I, T, E, C> {
+    let input = state.as_input().fork();
+    let bytes = input.as_bytes();
+    let is_digit =
+        |i: usize, radix: u32| bytes.get(i).is_some_and(|&b| (b as char).is_digit(radix));
+
+    let (negative, mut i) = match bytes.first() {
+        Some(b'-') if T::SIGNED => (true, 1),
+        Some(b'+') => (false, 1),
+        _ => (false, 0),
+    };
+
+    let radix = match radix {
+        Some(radix) => radix,
+        None => {
+            let prefixed = match bytes.get(i..i + 2) {
+                Some([b'0', b'x' | b'X']) => Some(16),
+                Some([b'0', b'o' | b'O']) => Some(8),
+                Some([b'0', b'b' | b'B']) => Some(2),
+                _ => None,
+            };
+
+            // NOTE: Only a prefix if a digit follows, so that `0x` alone is just `0`.
+            match prefixed {
+                Some(radix) if is_digit(i + 2, radix) => {
+                    i += 2;
+                    radix
+                }
+                _ => 10,
+            }
+        }
+    };
+
+    let start = i;
+    let mut value = Some(T::ZERO);
+    while let Some(&b) = bytes.get(i) {
+        match (b as char).to_digit(radix) {
+            Some(digit) => value = value.and_then(|v| v.push_digit(digit, radix, negative)),
+            None if b == b'_' && i > start => {}
+            None => break,
+        }
+
+        i += 1;
+    }
+
+    if i == start {
+        let len = decode_glyph(&bytes[start..]).map_or(1, |(_, len)| len);
+        return Err(state.with_error(Error::new(
+            ErrorKind::expected(ExpectedError::Digit(radix)),
+            input.skip(start).take(len),
+        )));
+    }
+
+    match value {
+        Some(value) => Ok((state.with_input(input.skip(i)), value)),
+        None => Err(state.with_error(Error::new(
+            ErrorKind::expected(ExpectedError::InRange(core::any::type_name::<T>())),
+            input.take(i),
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{errors::DummyError, input::Input};
+
+    fn parse<T: Integer>(input: &str) -> core::result::Result<T, Error<&str, DummyError>> {
+        integer::<T, _, _, ()>().parse(input)
+    }
+
+    #[test]
+    fn can_parse_integers() {
+        assert_eq!(parse::<u32>("0"), Ok(0));
+        assert_eq!(parse::<u32>("+42"), Ok(42));
+        assert_eq!(parse::<i32>("-42"), Ok(-42));
+        assert_eq!(parse::<u64>("1_000_000"), Ok(1_000_000));
+        assert_eq!(parse::<u32>("0xFF"), Ok(255));
+        assert_eq!(parse::<u32>("0o17"), Ok(15));
+        assert_eq!(parse::<i32>("-0b1010"), Ok(-10));
+        assert_eq!(
+            parse::<i128>("-0x8000_0000_0000_0000_0000_0000_0000_0000"),
+            Ok(i128::MIN)
+        );
+        assert_eq!(parse::<usize>("1_"), Ok(1));
+
+        let (state, parsed): (State<&str>, u8) = integer().process("0x;".into()).unwrap();
+        assert_eq!(parsed, 0);
+        assert_eq!(state.as_input(), &"x;");
+
+        // Unsigned types don't take a `-`
+        let (state, parsed): (State<&str>, u8) = integer().process("12-3".into()).unwrap();
+        assert_eq!(parsed, 12);
+        assert_eq!(state.as_input(), &"-3");
+    }
+
+    #[test]
+    fn integers_report_errors() {
+        for (input, span) in [("x", 0..1), ("-x", 1..2), ("_1", 0..1), ("", 0..0)] {
+            let state: State<&str> = integer::<i8, _, _, ()>().process(input.into()).unwrap_err();
+            assert_eq!(
+                state.errors(),
+                &Error::new(
+                    ErrorKind::expected(ExpectedError::Digit(10)),
+                    Input::new_with_span(input, span)
+                ),
+                "{:?}",
+                input
+            );
+        }
+
+        let state: State<&str> = integer::<u8, _, _, ()>().process("-1".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Digit(10)),
+                Input::new_with_span("-1", 0..1)
+            )
+        );
+    }
+
+    #[test]
+    fn integers_report_overflow() {
+        assert_eq!(parse::<i8>("127"), Ok(127));
+        assert_eq!(parse::<i8>("-128"), Ok(-128));
+
+        for (input, span) in [("128,", 0..3), ("-129,", 0..4), ("0x1_00,", 0..6)] {
+            let state: State<&str> = integer::<i8, _, _, ()>().process(input.into()).unwrap_err();
+            assert_eq!(state.as_input(), &input);
+            assert_eq!(
+                state.errors(),
+                &Error::new(
+                    ErrorKind::expected(ExpectedError::InRange("i8")),
+                    Input::new_with_span(input, span)
+                ),
+                "{:?}",
+                input
+            );
+        }
+
+        assert!(parse::<u128>("340282366920938463463374607431768211456").is_err());
+    }
+
+    #[test]
+    fn can_parse_explicit_radix() {
+        let parser = integer_radix::<u16, _, DummyError, ()>(16);
+        let (state, parsed) = parser.process("ffff_g".into()).unwrap();
+        assert_eq!(parsed, 0xffff);
+        assert_eq!(state.as_input(), &"g");
+
+        // No prefixes
+        let (state, parsed): (State<&str>, u16) = integer_radix(16).process("0x1".into()).unwrap();
+        assert_eq!(parsed, 0);
+        assert_eq!(state.as_input(), &"x1");
+
+        let state: State<&str> = integer_radix::<u8, _, _, ()>(2)
+            .process("2".into())
+            .unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Digit(2)),
+                Input::new_with_span("2", 0..1)
+            )
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use super::{dispatch::literals_first_set, CharClass, FirstSet};
+use crate::parser::{
+    errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
+    input::{decode_glyph, Input, Underlying},
+    state::State,
+    Parser,
+};
+
+/// The default identifier-character predicate for `keyword` and `keywords`: ASCII letters, digits
+/// and `_`. When the `unicode` feature is enabled, any character with the `XID_Continue` property
+/// (which includes `_`).
+#[inline]
+pub fn is_identifier_char(c: char) -> bool {
+    #[cfg(not(feature = "unicode"))]
+    {
+        c.is_ascii_alphanumeric() || c == '_'
+    }
+    #[cfg(feature = "unicode")]
+    {
+        unicode_ident::is_xid_continue(c)
+    }
+}
+
+/// Parses a keyword. Like `is`, but the keyword must *not* be followed by an identifier character
+/// (see `is_identifier_char`), so that `keyword("if")` does not match the start of `iffy`. The end
+/// of input is a valid word boundary. Use `Keyword::with_ident_chars` to change what counts as an
+/// identifier character.
+///
+/// ```
+/// # use errgonomic::combinators::keyword;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::errors::DummyError;
+/// let (state, parsed): (State<&str>, Input<&str>) = keyword("if").process("if x".into()).unwrap();
+/// assert_eq!(parsed, "if");
+/// assert_eq!(state.as_input().as_inner(), " x");
+///
+/// assert!(keyword::<_, DummyError, ()>("if").parse("iffy").is_err());
+/// ```
+pub fn keyword<I: Underlying, E: CustomError, C>(kw: I) -> Keyword<I, E, C> {
+    Keyword {
+        kw,
+        is_ident_char: is_identifier_char,
+        _marker: std::marker::PhantomData,
+    }
+}
+
+/// Parses any of the given keywords (see `keyword`), always preferring the longest one that
+/// matches. If none match, all the errors are returned as a single `All` error, like `any`.
+///
+/// ```
+/// # use errgonomic::combinators::keywords;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// let (state, parsed): (State<&str>, Input<&str>) =
+///     keywords(["in", "int", "if"]).process("int x".into()).unwrap();
+/// assert_eq!(parsed, "int");
+/// assert_eq!(state.as_input().as_inner(), " x");
+/// ```
+pub fn keywords<I: Underlying, E: CustomError, C, K: IntoIterator<Item = I>>(
+    kws: K,
+) -> Keywords<I, E, C> {
+    let mut kws = kws.into_iter().collect::<Vec<_>>();
+    // NOTE: Stable, so equal-length keywords keep their order (and thus error order).
+    kws.sort_by_key(|kw| core::cmp::Reverse(kw.len()));
+
+    Keywords {
+        kws,
+        is_ident_char: is_identifier_char,
+        _marker: std::marker::PhantomData,
+    }
+}
+
+/// A keyword parser. See `keyword`.
+#[derive(Debug, Clone)]
+pub struct Keyword<I: Underlying, E: CustomError, C = (), F = fn(char) -> bool> {
+    /// The keyword to match.
+    kw: I,
+
+    /// Whether a character is an identifier character, and thus can't follow the keyword.
+    is_ident_char: F,
+
+    _marker: std::marker::PhantomData<(E, C)>,
+}
+
+impl<I: Underlying, E: CustomError, C, F: Fn(char) -> bool> Keyword<I, E, C, F> {
+    /// Changes what counts as an identifier character, i.e. what can't directly follow the
+    /// keyword.
+    ///
+    /// ```
+    /// # use errgonomic::combinators::keyword;
+    /// # use errgonomic::parser::Parser;
+    /// # use errgonomic::parser::errors::DummyError;
+    /// // Lisp-style identifiers can contain `-`
+    /// let mut parser = keyword::<_, DummyError, ()>("let")
+    ///     .with_ident_chars(|c| c.is_ascii_alphanumeric() || c == '-');
+    /// assert!(parser.parse("let-values").is_err());
+    /// assert_eq!(parser.parse("let").unwrap(), "let");
+    /// ```
+    pub fn with_ident_chars<F2: Fn(char) -> bool>(self, f: F2) -> Keyword<I, E, C, F2> {
+        Keyword {
+            kw: self.kw,
+            is_ident_char: f,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I: Underlying, E: CustomError, C, F: Fn(char) -> bool> Parser<I, Input<I>, E, C>
+    for Keyword<I, E, C, F>
+{
+    fn process(&self, state: State<I, E, C>) -> Result<I, Input<I>, E, C> {
+        let input = state.as_input().fork();
+        match match_keyword(&input, &self.kw, &self.is_ident_char) {
+            Ok(len) => Ok((state.with_input(input.skip(len)), input.take(len))),
+            Err(e) => Err(state.with_error(e)),
+        }
+    }
+}
+
+impl<I: Underlying, E: CustomError, C, F> FirstSet for Keyword<I, E, C, F> {
+    fn first_set(&self) -> CharClass {
+        literals_first_set([keyword_bytes(&self.kw)])
+    }
+}
+
+/// A table of keywords. See `keywords`.
+#[derive(Debug, Clone)]
+pub struct Keywords<I: Underlying, E: CustomError, C = (), F = fn(char) -> bool> {
+    /// The keywords to match, longest first.
+    kws: Vec<I>,
+
+    /// Whether a character is an identifier character, and thus can't follow a keyword.
+    is_ident_char: F,
+
+    _marker: std::marker::PhantomData<(E, C)>,
+}
+
+impl<I: Underlying, E: CustomError, C, F: Fn(char) -> bool> Keywords<I, E, C, F> {
+    /// Changes what counts as an identifier character, i.e. what can't directly follow a keyword.
+    /// See `Keyword::with_ident_chars`.
+    pub fn with_ident_chars<F2: Fn(char) -> bool>(self, f: F2) -> Keywords<I, E, C, F2> {
+        Keywords {
+            kws: self.kws,
+            is_ident_char: f,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I: Underlying, E: CustomError, C, F: Fn(char) -> bool> Parser<I, Input<I>, E, C>
+    for Keywords<I, E, C, F>
+{
+    fn process(&self, state: State<I, E, C>) -> Result<I, Input<I>, E, C> {
+        assert!(!self.kws.is_empty(), "There should be at least 1 keyword!");
+        let input = state.as_input().fork();
+        let mut errs = vec![];
+
+        for kw in self.kws.iter() {
+            match match_keyword(&input, kw, &self.is_ident_char) {
+                Ok(len) => return Ok((state.with_input(input.skip(len)), input.take(len))),
+                Err(e) => errs.push(e),
+            }
+        }
+
+        let from = errs
+            .iter()
+            .map(|err| err.from())
+            .reduce(|acc, x| acc.join_between(&x))
+            .expect("There to be at least 1 error");
+
+        Err(state.with_error(Error::new(ErrorKind::all(errs), from)))
+    }
+}
+
+impl<I: Underlying, E: CustomError, C, F> FirstSet for Keywords<I, E, C, F> {
+    fn first_set(&self) -> CharClass {
+        literals_first_set(self.kws.iter().map(keyword_bytes))
+    }
+}
+
+/// INTERNAL: Matches `kw` at the start of `input`, followed by a word boundary. Returns the length
+/// of the keyword if it matches, or the error if it doesn't.
+fn match_keyword<I: Underlying, E: CustomError, F: Fn(char) -> bool>(
+    input: &Input<I>,
+    kw: &I,
+    is_ident_char: F,
+) -> core::result::Result<usize, Error<I, E>> {
+    let bytes = input.as_bytes();
+    let kw_bytes = keyword_bytes(kw);
+    let error = |from| Error::new(ErrorKind::expected(ExpectedError::Keyword(kw.fork())), from);
+
+    if !bytes.starts_with(kw_bytes) {
+        // NOTE: Same span as `is`, i.e. from the start up to and including the first mismatched byte
+        // (or the end of the input, if it ran out).
+        let matched_len = bytes
+            .iter()
+            .zip(kw_bytes)
+            .take_while(|(a, b)| a == b)
+            .count();
+        return Err(error(if matched_len < bytes.len() {
+            input.take(matched_len + 1)
+        } else {
+            input.skip(matched_len)
+        }));
+    }
+
+    match decode_glyph(&bytes[kw_bytes.len()..]) {
+        Some((c, n)) if is_ident_char(c) => Err(error(input.take(kw_bytes.len() + n))),
+        _ => Ok(kw_bytes.len()),
+    }
+}
+
+/// INTERNAL: The bytes of a keyword.
+fn keyword_bytes<I: Underlying>(kw: &I) -> &[u8] {
+    kw.byte_span(0, kw.len())
+        .expect("the keyword to cover itself")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_keyword() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            keyword("if").process("if(x)".into()).unwrap();
+        assert_eq!(parsed, "if");
+        assert_eq!(state.as_input(), &"(x)");
+        assert!(!state.is_err());
+
+        // End of input is a word boundary
+        let (state, parsed): (State<&str>, Input<&str>) =
+            keyword("if").process("if".into()).unwrap();
+        assert_eq!(parsed, "if");
+        assert_eq!(state.as_input(), &"");
+    }
+
+    #[test]
+    fn keyword_checks_word_boundary() {
+        let state: State<&str> = keyword("if").process("iffy".into()).unwrap_err();
+        assert_eq!(state.as_input(), &"iffy");
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Keyword("if")),
+                Input::new_with_span("iffy", 0..3)
+            )
+        );
+        assert_eq!(state.errors().to_string(), "expected keyword `if`");
+
+        let state: State<&str> = keyword("if").process("if_".into()).unwrap_err();
+        assert!(state.is_err());
+
+        let state: State<&str> = keyword("else").process("elif".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Keyword("else")),
+                Input::new_with_span("elif", 0..3)
+            )
+        );
+
+        let state: State<&str> = keyword("else").process("el".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Keyword("else")),
+                Input::new_with_span("el", 2..2)
+            )
+        );
+    }
+
+    #[test]
+    fn keyword_with_ident_chars() {
+        let (state, parsed): (State<&str>, Input<&str>) = keyword("if")
+            .with_ident_chars(|c| c.is_ascii_alphabetic())
+            .process("if_1".into())
+            .unwrap();
+        assert_eq!(parsed, "if");
+        assert_eq!(state.as_input(), &"_1");
+    }
+
+    #[test]
+    fn can_parse_keywords() {
+        let parser = keywords(["in", "int", "if"]);
+
+        let (state, parsed): (State<&str>, Input<&str>) = parser.process("in x".into()).unwrap();
+        assert_eq!(parsed, "in");
+        assert_eq!(state.as_input(), &" x");
+
+        let (state, parsed): (State<&str>, Input<&str>) = parser.process("int".into()).unwrap();
+        assert_eq!(parsed, "int");
+        assert_eq!(state.as_input(), &"");
+
+        let state: State<&str> = parser.process("inside".into()).unwrap_err();
+        assert_eq!(state.as_input(), &"inside");
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::All(vec![
+                    Error::new(
+                        ErrorKind::expected(ExpectedError::Keyword("int")),
+                        Input::new_with_span("inside", 0..3)
+                    ),
+                    Error::new(
+                        ErrorKind::expected(ExpectedError::Keyword("in")),
+                        Input::new_with_span("inside", 0..3)
+                    ),
+                    Error::new(
+                        ErrorKind::expected(ExpectedError::Keyword("if")),
+                        Input::new_with_span("inside", 0..2)
+                    ),
+                ]),
+                Input::new_with_span("inside", 0..3)
+            )
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn keyword_checks_unicode_word_boundary() {
+        let state: State<&str> = keyword("if").process("ifé".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Keyword("if")),
+                Input::new_with_span("ifé", 0..4)
+            )
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use super::{dispatch::literals_first_set, CharClass, FirstSet};
+use crate::parser::{
+    errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
+    input::{Input, Underlying},
+    state::State,
+    Parser,
+};
+
+/// Parses any of the given literals, always preferring the longest one that matches, no matter
+/// the order they are given in. The literals are compiled into a trie once, so the input is only
+/// scanned once, and there is no limit on how many there are (unlike the tuples for `any`).
+///
+/// If none match, all the literals are returned as `Is` errors in a single `All` error, like
+/// `any`.
+///
+/// ```
+/// # use errgonomic::combinators::literals;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// let (state, parsed): (State<&str>, Input<&str>) =
+///     literals(["<", "<=", "<<", "<<="]).process("<<= 1".into()).unwrap();
+/// assert_eq!(parsed, "<<=");
+/// assert_eq!(state.as_input().as_inner(), " 1");
+/// ```
+pub fn literals<I: Underlying, E: CustomError, C, L: IntoIterator<Item = I>>(
+    literals: L,
+) -> Literals<I, E, C> {
+    Literals {
+        trie: Trie::new(literals.into_iter().map(|l| (l, ()))),
+        _marker: std::marker::PhantomData,
+    }
+}
+
+/// Like `literals`, but maps each literal to a value, which is returned instead of the input.
+///
+/// NOTE: If a literal is given more than once, the last value for it is used.
+///
+/// ```
+/// # use errgonomic::combinators::literal_map;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Op { Lt, Le, Shl, ShlAssign }
+///
+/// let ops = literal_map([("<", Op::Lt), ("<=", Op::Le), ("<<", Op::Shl), ("<<=", Op::ShlAssign)]);
+/// let (state, parsed): (State<&str>, Op) = ops.process("<< 1".into()).unwrap();
+/// assert_eq!(parsed, Op::Shl);
+/// assert_eq!(state.as_input().as_inner(), " 1");
+/// ```
+pub fn literal_map<I: Underlying, V: Clone, E: CustomError, C, L: IntoIterator<Item = (I, V)>>(
+    literals: L,
+) -> LiteralMap<I, V, E, C> {
+    LiteralMap {
+        trie: Trie::new(literals),
+        _marker: std::marker::PhantomData,
+    }
+}
+
+/// A table of literals. See `literals`.
+#[derive(Debug, Clone)]
+pub struct Literals<I: Underlying, E: CustomError, C = ()> {
+    trie: Trie<I, ()>,
+    _marker: std::marker::PhantomData<(E, C)>,
+}
+
+impl<I: Underlying, E: CustomError, C> Parser<I, Input<I>, E, C> for Literals<I, E, C> {
+    fn process(&self, state: State<I, E, C>) -> Result<I, Input<I>, E, C> {
+        let input = state.as_input().fork();
+        match self.trie.longest_match(input.as_bytes()) {
+            Some((len, _)) => Ok((state.with_input(input.skip(len)), input.take(len))),
+            None => Err(state.with_error(self.trie.error(&input))),
+        }
+    }
+}
+
+impl<I: Underlying, E: CustomError, C> FirstSet for Literals<I, E, C> {
+    fn first_set(&self) -> CharClass {
+        self.trie.first_set()
+    }
+}
+
+/// A table of literals mapped to values. See `literal_map`.
+#[derive(Debug, Clone)]
+pub struct LiteralMap<I: Underlying, V: Clone, E: CustomError, C = ()> {
+    trie: Trie<I, V>,
+    _marker: std::marker::PhantomData<(E, C)>,
+}
+
+impl<I: Underlying, V: Clone, E: CustomError, C> Parser<I, V, E, C> for LiteralMap<I, V, E, C> {
+    fn process(&self, state: State<I, E, C>) -> Result<I, V, E, C> {
+        let input = state.as_input().fork();
+        match self.trie.longest_match(input.as_bytes()) {
+            Some((len, value)) => Ok((state.with_input(input.skip(len)), value.clone())),
+            None => Err(state.with_error(self.trie.error(&input))),
+        }
+    }
+}
+
+impl<I: Underlying, V: Clone, E: CustomError, C> FirstSet for LiteralMap<I, V, E, C> {
+    fn first_set(&self) -> CharClass {
+        self.trie.first_set()
+    }
+}
+
+/// INTERNAL: A byte trie of literals, each with a value.
+#[derive(Debug, Clone)]
+struct Trie<I: Underlying, V> {
+    /// The literals and their values, in the order they were given (for errors).
+    entries: Vec<(I, V)>,
+
+    /// The nodes of the trie. The root is always the first node.
+    nodes: Vec<Node>,
+}
+
+/// INTERNAL: A node in a `Trie`.
+#[derive(Debug, Clone, Default)]
+struct Node {
+    /// The children of this node, sorted by byte.
+    children: Vec<(u8, usize)>,
+
+    /// The index of the entry that ends at this node, if any.
+    entry: Option<usize>,
+}
+
+impl<I: Underlying, V> Trie<I, V> {
+    /// Compiles the trie.
+    fn new<L: IntoIterator<Item = (I, V)>>(literals: L) -> Self {
+        let entries = literals.into_iter().collect::<Vec<_>>();
+        assert!(!entries.is_empty(), "There should be at least 1 literal!");
+        let mut nodes = vec![Node::default()];
+
+        for (i, (literal, _)) in entries.iter().enumerate() {
+            let mut node = 0;
+            for &b in literal_bytes(literal) {
+                node = match nodes[node].children.binary_search_by_key(&b, |&(c, _)| c) {
+                    Ok(child) => nodes[node].children[child].1,
+                    Err(child) => {
+                        nodes.push(Node::default());
+                        let new = nodes.len() - 1;
+                        nodes[node].children.insert(child, (b, new));
+                        new
+                    }
+                };
+            }
+
+            nodes[node].entry = Some(i);
+        }
+
+        Self { entries, nodes }
+    }
+
+    /// Gets the length and value of the longest literal at the start of `bytes`, if any.
+    fn longest_match(&self, bytes: &[u8]) -> Option<(usize, &V)> {
+        let mut node = &self.nodes[0];
+        let mut longest = node.entry.map(|entry| (0, entry));
+
+        for (i, &b) in bytes.iter().enumerate() {
+            match node.children.binary_search_by_key(&b, |&(c, _)| c) {
+                Ok(child) => node = &self.nodes[node.children[child].1],
+                Err(_) => break,
+            }
+
+            if let Some(entry) = node.entry {
+                longest = Some((i + 1, entry));
+            }
+        }
+
+        longest.map(|(len, entry)| (len, &self.entries[entry].1))
+    }
+
+    /// Gets the class of the first characters of the literals.
+    fn first_set(&self) -> CharClass {
+        literals_first_set(
+            self.entries
+                .iter()
+                .map(|(literal, _)| literal_bytes(literal)),
+        )
+    }
+
+    /// Gets the error for when no literal matches `input`: an `Is` error for every literal, each
+    /// spanning up to and including its first mismatched byte (like `is`).
+    fn error<E: CustomError>(&self, input: &Input<I>) -> Error<I, E> {
+        let bytes = input.as_bytes();
+        let errs = self
+            .entries
+            .iter()
+            .map(|(literal, _)| {
+                let matched_len = bytes
+                    .iter()
+                    .zip(literal_bytes(literal))
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                let from = if matched_len < bytes.len() {
+                    input.take(matched_len + 1)
+                } else {
+                    input.skip(matched_len)
+                };
+
+                Error::new(ErrorKind::expected(ExpectedError::Is(literal.fork())), from)
+            })
+            .collect::<Vec<_>>();
+
+        let from = errs
+            .iter()
+            .map(|err| err.from())
+            .reduce(|acc, x| acc.join_between(&x))
+            .expect("There to be at least 1 error");
+
+        Error::new(ErrorKind::all(errs), from)
+    }
+}
+
+/// INTERNAL: The bytes of a literal.
+fn literal_bytes<I: Underlying>(literal: &I) -> &[u8] {
+    literal
+        .byte_span(0, literal.len())
+        .expect("the literal to cover itself")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::errors::DummyError;
+
+    #[test]
+    fn can_parse_literals() {
+        let parser = literals(["<", "<=", "<<", "<<="]);
+
+        for (input, expected, rest) in [
+            ("<", "<", ""),
+            ("<=", "<=", ""),
+            ("<<", "<<", ""),
+            ("<<=", "<<=", ""),
+            ("<<<", "<<", "<"),
+            ("<=<", "<=", "<"),
+            ("< 1", "<", " 1"),
+        ] {
+            let (state, parsed): (State<&str>, Input<&str>) = parser.process(input.into()).unwrap();
+            assert_eq!(parsed, expected, "{:?}", input);
+            assert_eq!(state.as_input(), &rest, "{:?}", input);
+            assert!(!state.is_err());
+        }
+    }
+
+    #[test]
+    fn literals_backtrack_to_longest_match() {
+        // `ab` is a prefix of `abcd`, but `abc` isn't a literal
+        let parser = literals(["a", "ab", "abcd"]);
+        let (state, parsed): (State<&str>, Input<&str>) = parser.process("abcx".into()).unwrap();
+        assert_eq!(parsed, "ab");
+        assert_eq!(state.as_input(), &"cx");
+    }
+
+    #[test]
+    fn literals_report_expected_set() {
+        let state: State<&str> = literals(["==", "!=", "<"])
+            .process("=<".into())
+            .unwrap_err();
+        assert_eq!(state.as_input(), &"=<");
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::All(vec![
+                    Error::new(
+                        ErrorKind::expected(ExpectedError::Is("==")),
+                        Input::new_with_span("=<", 0..2)
+                    ),
+                    Error::new(
+                        ErrorKind::expected(ExpectedError::Is("!=")),
+                        Input::new_with_span("=<", 0..1)
+                    ),
+                    Error::new(
+                        ErrorKind::expected(ExpectedError::Is("<")),
+                        Input::new_with_span("=<", 0..1)
+                    ),
+                ]),
+                Input::new_with_span("=<", 0..2)
+            )
+        );
+        assert_eq!(
+            state.errors().to_string(),
+            "expected `==`, or expected `!=`, or expected `<`"
+        );
+
+        let state: State<&str> = literals(["=="]).process("=".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::All(vec![Error::new(
+                    ErrorKind::expected(ExpectedError::Is("==")),
+                    Input::new_with_span("=", 1..1)
+                )]),
+                Input::new_with_span("=", 1..1)
+            )
+        );
+    }
+
+    #[test]
+    fn can_parse_literal_map() {
+        let parser = literal_map([("true", true), ("false", false), ("yes", true)]);
+        assert_eq!(
+            Parser::<_, _, DummyError>::parse(&mut parser.clone(), "yes"),
+            Ok(true)
+        );
+        assert_eq!(
+            Parser::<_, _, DummyError>::parse(&mut parser.clone(), "false"),
+            Ok(false)
+        );
+
+        // The last value wins
+        let (_, parsed): (State<&str>, u32) = literal_map([("a", 1), ("a", 2)])
+            .process("a".into())
+            .unwrap();
+        assert_eq!(parsed, 2);
+    }
+
+    #[test]
+    fn can_parse_many_literals() {
+        // More than the 20 alternatives `any` supports
+        let words = (0..100).map(|i| format!("w{}", i)).collect::<Vec<_>>();
+        let parser = literal_map(words.iter().enumerate().map(|(i, w)| (w.as_str(), i)));
+
+        for (i, w) in words.iter().enumerate() {
+            let (state, parsed): (State<&str>, usize) = parser.process(w.as_str().into()).unwrap();
+            assert_eq!(parsed, i);
+            assert!(state.as_input().is_empty());
+        }
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn can_parse_unicode_literals() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            literals(["→", "⇒", "=>"]).process("⇒x".into()).unwrap();
+        assert_eq!(parsed, "⇒");
+        assert_eq!(state.as_input(), &"x");
+    }
+}
@@ -0,0 +1,159 @@
+use crate::parser::{errors::CustomError, input::Underlying, state::State, Parser};
+
+/// Runs the parser and returns its output, but doesn't consume any input. If the parser fails, its
+/// error is returned.
+///
+/// ```
+/// # use errgonomic::combinators::{peek, decimal};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// let (state, parsed): (State<&str>, Input<&str>) = peek(decimal).process("123abc".into()).unwrap();
+/// assert_eq!(parsed, "123");
+/// assert_eq!(state.as_input().as_inner(), "123abc");
+/// ```
+pub fn peek<I: Underlying, O, E: CustomError, C, P: Parser<I, O, E, C>>(
+    p: P,
+) -> impl Parser<I, O, E, C> {
+    move |state: State<I, E, C>| match p.process(state.fork()) {
+        Ok((_, o)) => Ok((state, o)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Positive lookahead: succeeds without consuming any input if the parser matches, and fails with
+/// its error otherwise. The opposite of `not`.
+///
+/// Useful after `then` to only parse something if it's followed by something else, e.g. `<` as
+/// the start of generics rather than a comparison:
+///
+/// ```
+/// # use errgonomic::combinators::{lookahead, is, alphabetic};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// let generics = is("<").then(lookahead(alphabetic.then(is(">"))));
+///
+/// let (state, _): (State<&str>, (Input<&str>, ())) = generics.process("<T>".into()).unwrap();
+/// assert_eq!(state.as_input().as_inner(), "T>");
+///
+/// let state: State<&str> = generics.process("< 1".into()).unwrap_err();
+/// assert!(state.is_err());
+/// ```
+pub fn lookahead<I: Underlying, O, E: CustomError, C, P: Parser<I, O, E, C>>(
+    p: P,
+) -> impl Parser<I, (), E, C> {
+    move |state: State<I, E, C>| match p.process(state.fork()) {
+        Ok(_) => Ok((state, ())),
+        Err(e) => Err(e),
+    }
+}
+
+/// A guarded alternative: `guard` is checked first, at the start of `body` (without consuming
+/// anything, like `lookahead`). If it matches, then `body` is parsed and committed to, so that an
+/// enclosing `any` doesn't try any other branches if it fails. If `guard` doesn't match, its
+/// (uncommitted) error is returned, so the next branch is tried.
+///
+/// ```
+/// # use errgonomic::combinators::{any, guarded, is, decimal, alphabetic};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// // Only `<` followed by a letter starts generics, anything else is a comparison
+/// let generics = guarded(is("<").then(alphabetic), is("<").then(alphabetic).then(is(">")))
+///     .map(|_| "generics");
+/// let comparison = is("<").then(decimal).map(|_| "comparison");
+/// let parser = any((generics, comparison));
+///
+/// let (_, parsed): (State<&str>, &str) = parser.process("<T>".into()).unwrap();
+/// assert_eq!(parsed, "generics");
+/// let (_, parsed): (State<&str>, &str) = parser.process("<1".into()).unwrap();
+/// assert_eq!(parsed, "comparison");
+///
+/// // Once it looks like generics, it has to be generics
+/// let state: State<&str> = parser.process("<T1".into()).unwrap_err();
+/// assert!(state.errors().is_committed());
+/// ```
+pub fn guarded<I: Underlying, O1, O2, E: CustomError, C, P1, P2>(
+    guard: P1,
+    body: P2,
+) -> impl Parser<I, O2, E, C>
+where
+    P1: Parser<I, O1, E, C>,
+    P2: Parser<I, O2, E, C>,
+{
+    move |state: State<I, E, C>| {
+        guard.process(state.fork())?;
+
+        match body.process(state) {
+            Ok(x) => Ok(x),
+            Err(e) => Err(e.commit()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        combinators::{any, decimal, is},
+        parser::{
+            errors::{Error, ErrorKind, ExpectedError},
+            input::Input,
+        },
+    };
+
+    #[test]
+    fn can_peek() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            peek(is("ab")).process("abc".into()).unwrap();
+        assert_eq!(parsed, "ab");
+        assert_eq!(state.as_input(), &"abc");
+        assert!(!state.is_err());
+
+        let state: State<&str> = peek(is("ab")).process("ac".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Is("ab")),
+                Input::new_with_span("ac", 0..2)
+            )
+        );
+    }
+
+    #[test]
+    fn can_lookahead() {
+        let (state, parsed): (State<&str>, (Input<&str>, ())) = decimal
+            .then(lookahead(is(";")))
+            .process("12;".into())
+            .unwrap();
+        assert_eq!(parsed.0, "12");
+        assert_eq!(state.as_input(), &";");
+
+        let state: State<&str> = decimal
+            .then(lookahead(is(";")))
+            .process("12,".into())
+            .unwrap_err();
+        assert!(state.is_err());
+    }
+
+    #[test]
+    fn guarded_commits_to_branch() {
+        let parser = any((
+            guarded(is("#"), is("#!").then(decimal).map(|(_, n)| n)),
+            is("#").then(decimal).map(|(_, n)| n),
+        ));
+
+        let (_, parsed): (State<&str>, Input<&str>) = parser.process("#!1".into()).unwrap();
+        assert_eq!(parsed, "1");
+
+        // The guard matched, so the second branch is never tried
+        let state: State<&str> = parser.process("#1".into()).unwrap_err();
+        assert!(state.errors().is_committed());
+
+        // The guard didn't match, so the next branch is tried
+        let parser = any((guarded(is("!"), is("!!")), is("?")));
+        let (_, parsed): (State<&str>, Input<&str>) = parser.process("?".into()).unwrap();
+        assert_eq!(parsed, "?");
+    }
+}
@@ -1,13 +1,17 @@
 use crate::parser::{
-    errors::{CustomError, Result},
+    errors::{CustomError, Error, ErrorKind, Result},
     input::Underlying,
     state::State,
     Parser,
 };
+use core::panic::Location;
 
 /// Parses as many of the given parser as possible. At the first error, returns all the parsed
 /// output that happened before the error. If it errors out on the first parser, it will return
 /// an empty list.
+///
+/// NOTE: Like every repetition combinator, if the parser succeeds without consuming anything, a
+/// `NoProgress` error is returned instead of looping forever.
 ///```
 /// # use errgonomic::combinators::{many, is};
 /// # use errgonomic::parser::Parser;
@@ -17,13 +21,20 @@ use crate::parser::{
 /// assert_eq!(parsed, vec!["hello", "hello", "hello"]);
 /// assert_eq!(state.as_input().as_inner(), ", world!");
 ///```
-pub fn many<I: Underlying, O, E: CustomError, P: Parser<I, O, E>>(
+#[track_caller]
+pub fn many<I: Underlying, O, E: CustomError, C, P: Parser<I, O, E, C>>(
     p: P,
-) -> impl Parser<I, Vec<O>, E> {
-    move |mut state: State<I, E>| -> Result<I, Vec<O>, E> {
+) -> impl Parser<I, Vec<O>, E, C> {
+    let location = caller();
+
+    move |mut state: State<I, E, C>| -> Result<I, Vec<O>, E, C> {
         let mut results = Vec::new();
 
         while let Ok((new_state, o)) = p.process(state.fork()) {
+            if !progressed(&state, &new_state) {
+                return Err(no_progress(state, location));
+            }
+
             state = new_state;
             results.push(o);
         }
@@ -48,24 +59,30 @@ pub fn many<I: Underlying, O, E: CustomError, P: Parser<I, O, E>>(
 /// assert_eq!(parsed, vec!["hello", "hello"]);
 /// assert_eq!(state.as_input().as_inner(), ", world!");
 ///
-/// let (state, parsed) = many_n(2, is::<_, DummyError>("hello")).process("hellohello, world!".into()).unwrap();
+/// let (state, parsed) = many_n(2, is::<_, DummyError, ()>("hello")).process("hellohello, world!".into()).unwrap();
 /// assert_eq!(parsed, vec!["hello", "hello"]);
 /// assert_eq!(state.as_input().as_inner(), ", world!");
 ///
-/// let state = many_n(3, is::<_, DummyError>("hello")).process("hellohello, world!".into()).unwrap_err();
+/// let state = many_n(3, is::<_, DummyError, ()>("hello")).process("hellohello, world!".into()).unwrap_err();
 /// assert!(state.is_err());
 /// assert_eq!(state.as_input().as_inner(), ", world!");
 ///```
-pub fn many_n<I: Underlying, O, E: CustomError, P: Parser<I, O, E>>(
+#[track_caller]
+pub fn many_n<I: Underlying, O, E: CustomError, C, P: Parser<I, O, E, C>>(
     n: usize,
     p: P,
-) -> impl Parser<I, Vec<O>, E> {
-    move |mut state: State<I, E>| -> Result<I, Vec<O>, E> {
+) -> impl Parser<I, Vec<O>, E, C> {
+    let location = caller();
+
+    move |mut state: State<I, E, C>| -> Result<I, Vec<O>, E, C> {
         let mut results = Vec::new();
 
         for _ in 0..n {
             match p.process(state.fork()) {
                 Ok((new_state, o)) => {
+                    if !progressed(&state, &new_state) {
+                        return Err(no_progress(state, location));
+                    }
                     state = new_state;
                     results.push(o);
                 }
@@ -74,6 +91,10 @@ pub fn many_n<I: Underlying, O, E: CustomError, P: Parser<I, O, E>>(
         }
 
         while let Ok((new_state, o)) = p.process(state.fork()) {
+            if !progressed(&state, &new_state) {
+                return Err(no_progress(state, location));
+            }
+
             state = new_state;
             results.push(o);
         }
@@ -91,33 +112,39 @@ pub fn many_n<I: Underlying, O, E: CustomError, P: Parser<I, O, E>>(
 /// # use errgonomic::parser::state::State;
 /// # use errgonomic::parser::input::Input;
 /// # use errgonomic::parser::errors::DummyError;
-/// let state = many_m_n(1, 2, is::<_, DummyError>("hello")).process(", world!".into()).unwrap_err();
+/// let state = many_m_n(1, 2, is::<_, DummyError, ()>("hello")).process(", world!".into()).unwrap_err();
 /// assert!(state.is_err());
 /// assert_eq!(state.as_input().as_inner(), ", world!");
 ///
-/// let (state, parsed) = many_m_n(1, 2, is::<_, DummyError>("hello")).process("hello, world!".into()).unwrap();
+/// let (state, parsed) = many_m_n(1, 2, is::<_, DummyError, ()>("hello")).process("hello, world!".into()).unwrap();
 /// assert_eq!(parsed, vec!["hello"]);
 /// assert_eq!(state.as_input().as_inner(), ", world!");
 ///
-/// let (state, parsed) = many_m_n(1, 2, is::<_, DummyError>("hello")).process("hellohello, world!".into()).unwrap();
+/// let (state, parsed) = many_m_n(1, 2, is::<_, DummyError, ()>("hello")).process("hellohello, world!".into()).unwrap();
 /// assert_eq!(parsed, vec!["hello", "hello"]);
 /// assert_eq!(state.as_input().as_inner(), ", world!");
 ///
-/// let (state, parsed) = many_m_n(1, 2, is::<_, DummyError>("hello")).process("hellohellohello, world!".into()).unwrap();
+/// let (state, parsed) = many_m_n(1, 2, is::<_, DummyError, ()>("hello")).process("hellohellohello, world!".into()).unwrap();
 /// assert_eq!(parsed, vec!["hello", "hello"]);
 /// assert_eq!(state.as_input().as_inner(), "hello, world!");
 ///```
-pub fn many_m_n<I: Underlying, O, E: CustomError, P: Parser<I, O, E>>(
+#[track_caller]
+pub fn many_m_n<I: Underlying, O, E: CustomError, C, P: Parser<I, O, E, C>>(
     m: usize,
     n: usize,
     p: P,
-) -> impl Parser<I, Vec<O>, E> {
-    move |mut state: State<I, E>| -> Result<I, Vec<O>, E> {
+) -> impl Parser<I, Vec<O>, E, C> {
+    let location = caller();
+
+    move |mut state: State<I, E, C>| -> Result<I, Vec<O>, E, C> {
         let mut results = Vec::new();
 
         for _ in 0..m {
             match p.process(state.fork()) {
                 Ok((new_state, o)) => {
+                    if !progressed(&state, &new_state) {
+                        return Err(no_progress(state, location));
+                    }
                     state = new_state;
                     results.push(o);
                 }
@@ -128,6 +155,9 @@ pub fn many_m_n<I: Underlying, O, E: CustomError, P: Parser<I, O, E>>(
         for _ in m..n {
             match p.process(state.fork()) {
                 Ok((new_state, o)) => {
+                    if !progressed(&state, &new_state) {
+                        return Err(no_progress(state, location));
+                    }
                     state = new_state;
                     results.push(o);
                 }
@@ -153,24 +183,32 @@ pub fn many_m_n<I: Underlying, O, E: CustomError, P: Parser<I, O, E>>(
 /// assert_eq!(parsed.1, ", world!");
 /// assert_eq!(state.as_input().as_inner(), " Hi!");
 ///```
+#[track_caller]
 pub fn many_until<
     I: Underlying,
     O1,
     O2,
     E: CustomError,
-    P1: Parser<I, O1, E>,
-    P2: Parser<I, O2, E>,
+    C,
+    P1: Parser<I, O1, E, C>,
+    P2: Parser<I, O2, E, C>,
 >(
     p: P1,
     until: P2,
-) -> impl Parser<I, (Vec<O1>, O2), E> {
-    move |mut state: State<I, E>| -> Result<I, (Vec<O1>, O2), E> {
+) -> impl Parser<I, (Vec<O1>, O2), E, C> {
+    let location = caller();
+
+    move |mut state: State<I, E, C>| -> Result<I, (Vec<O1>, O2), E, C> {
         let mut results = Vec::new();
 
         loop {
             match until.process(state.fork()) {
                 Err(_) => {
                     let (new_state, o) = p.process(state.fork())?;
+                    if !progressed(&state, &new_state) {
+                        return Err(no_progress(state, location));
+                    }
+
                     state = new_state;
                     results.push(o);
                 }
@@ -180,10 +218,248 @@ pub fn many_until<
     }
 }
 
+/// Like `many`, but folds each output into an accumulator instead of collecting them into a
+/// `Vec`. `init` creates the starting accumulator (every time the parser is run), and `f` folds an
+/// output into it.
+///```
+/// # use errgonomic::combinators::{fold_many, decimal, whitespace_wrapped as ww};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let sum = fold_many(ww(decimal), || 0, |acc, n: Input<&str>| acc + n.as_inner().parse::<u32>().unwrap());
+/// let (state, parsed): (State<&str>, u32) = sum.process("1 2 3!".into()).unwrap();
+/// assert_eq!(parsed, 6);
+/// assert_eq!(state.as_input().as_inner(), "!");
+///```
+#[track_caller]
+pub fn fold_many<I: Underlying, O, B, E: CustomError, C, P, Init, F>(
+    p: P,
+    init: Init,
+    f: F,
+) -> impl Parser<I, B, E, C>
+where
+    P: Parser<I, O, E, C>,
+    Init: Fn() -> B,
+    F: Fn(B, O) -> B,
+{
+    let location = caller();
+
+    move |mut state: State<I, E, C>| -> Result<I, B, E, C> {
+        let mut acc = init();
+
+        while let Ok((new_state, o)) = p.process(state.fork()) {
+            if !progressed(&state, &new_state) {
+                return Err(no_progress(state, location));
+            }
+
+            state = new_state;
+            acc = f(acc, o);
+        }
+
+        Ok((state, acc))
+    }
+}
+
+/// Like `many_n`, but folds each output into an accumulator. See `fold_many`.
+///```
+/// # use errgonomic::combinators::{fold_many_n, is};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::errors::DummyError;
+/// let mut count = fold_many_n(2, is::<_, DummyError, ()>("a"), || 0, |acc, _| acc + 1);
+/// assert_eq!(count.parse("aaa").unwrap(), 3);
+/// assert!(count.parse("a").is_err());
+///```
+#[track_caller]
+pub fn fold_many_n<I: Underlying, O, B, E: CustomError, C, P, Init, F>(
+    n: usize,
+    p: P,
+    init: Init,
+    f: F,
+) -> impl Parser<I, B, E, C>
+where
+    P: Parser<I, O, E, C>,
+    Init: Fn() -> B,
+    F: Fn(B, O) -> B,
+{
+    let location = caller();
+
+    move |mut state: State<I, E, C>| -> Result<I, B, E, C> {
+        let mut acc = init();
+
+        for _ in 0..n {
+            let (new_state, o) = p.process(state.fork())?;
+            if !progressed(&state, &new_state) {
+                return Err(no_progress(state, location));
+            }
+
+            state = new_state;
+            acc = f(acc, o);
+        }
+
+        while let Ok((new_state, o)) = p.process(state.fork()) {
+            if !progressed(&state, &new_state) {
+                return Err(no_progress(state, location));
+            }
+
+            state = new_state;
+            acc = f(acc, o);
+        }
+
+        Ok((state, acc))
+    }
+}
+
+/// Like `many_m_n`, but folds each output into an accumulator. See `fold_many`.
+///```
+/// # use errgonomic::combinators::{fold_many_m_n, is};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// let (state, parsed): (State<&str>, usize) = fold_many_m_n(1, 2, is("a"), || 0, |acc, _| acc + 1)
+///     .process("aaa".into())
+///     .unwrap();
+/// assert_eq!(parsed, 2);
+/// assert_eq!(state.as_input().as_inner(), "a");
+///```
+#[track_caller]
+pub fn fold_many_m_n<I: Underlying, O, B, E: CustomError, C, P, Init, F>(
+    m: usize,
+    n: usize,
+    p: P,
+    init: Init,
+    f: F,
+) -> impl Parser<I, B, E, C>
+where
+    P: Parser<I, O, E, C>,
+    Init: Fn() -> B,
+    F: Fn(B, O) -> B,
+{
+    let location = caller();
+
+    move |mut state: State<I, E, C>| -> Result<I, B, E, C> {
+        let mut acc = init();
+
+        for _ in 0..m {
+            let (new_state, o) = p.process(state.fork())?;
+            if !progressed(&state, &new_state) {
+                return Err(no_progress(state, location));
+            }
+
+            state = new_state;
+            acc = f(acc, o);
+        }
+
+        for _ in m..n {
+            match p.process(state.fork()) {
+                Ok((new_state, o)) => {
+                    if !progressed(&state, &new_state) {
+                        return Err(no_progress(state, location));
+                    }
+                    state = new_state;
+                    acc = f(acc, o);
+                }
+                Err(_) => break,
+            }
+        }
+
+        Ok((state, acc))
+    }
+}
+
+/// Like `many`, but collects the outputs into any collection that can be extended (e.g.
+/// `HashMap`, `String`, `BTreeSet`, ...), instead of a `Vec`.
+///```
+/// # use std::collections::HashMap;
+/// # use errgonomic::combinators::{many_into, separated_pair, alphabetic, decimal, is};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let pair = separated_pair(alphabetic, is("="), decimal)
+///     .map(|(k, v): (Input<&str>, Input<&str>)| (k.as_inner().to_string(), v.as_inner().to_string()));
+/// let (_, parsed): (State<&str>, HashMap<String, String>) =
+///     many_into(pair).process("a=1b=2".into()).unwrap();
+/// assert_eq!(parsed.len(), 2);
+/// assert_eq!(parsed["b"], "2");
+///```
+#[track_caller]
+pub fn many_into<I: Underlying, O, T, E: CustomError, C, P: Parser<I, O, E, C>>(
+    p: P,
+) -> impl Parser<I, T, E, C>
+where
+    T: Default + Extend<O>,
+{
+    fold_many(p, T::default, extend)
+}
+
+/// Like `many_n`, but collects the outputs into any collection that can be extended. See
+/// `many_into`.
+#[track_caller]
+pub fn many_n_into<I: Underlying, O, T, E: CustomError, C, P: Parser<I, O, E, C>>(
+    n: usize,
+    p: P,
+) -> impl Parser<I, T, E, C>
+where
+    T: Default + Extend<O>,
+{
+    fold_many_n(n, p, T::default, extend)
+}
+
+/// Like `many_m_n`, but collects the outputs into any collection that can be extended. See
+/// `many_into`.
+#[track_caller]
+pub fn many_m_n_into<I: Underlying, O, T, E: CustomError, C, P: Parser<I, O, E, C>>(
+    m: usize,
+    n: usize,
+    p: P,
+) -> impl Parser<I, T, E, C>
+where
+    T: Default + Extend<O>,
+{
+    fold_many_m_n(m, n, p, T::default, extend)
+}
+
+/// INTERNAL: Where a repetition combinator was created, for `NoProgress` errors. Only tracked in
+/// debug builds.
+#[track_caller]
+#[inline]
+pub(crate) fn caller() -> Option<&'static Location<'static>> {
+    if cfg!(debug_assertions) {
+        Some(Location::caller())
+    } else {
+        None
+    }
+}
+
+/// INTERNAL: Whether a repeated parser consumed anything going from `before` to `after`.
+#[inline]
+pub(crate) fn progressed<I: Underlying, E: CustomError, C>(
+    before: &State<I, E, C>,
+    after: &State<I, E, C>,
+) -> bool {
+    after.as_input().span().head() != before.as_input().span().head()
+}
+
+/// INTERNAL: The error for a repeated parser that didn't consume anything.
+pub(crate) fn no_progress<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+    location: Option<&'static Location<'static>>,
+) -> State<I, E, C> {
+    let input = state.as_input().take(0);
+    state.with_error(Error::new(ErrorKind::no_progress(location), input))
+}
+
+/// INTERNAL: Adds an output to a collection, for folding.
+pub(crate) fn extend<O, C: Extend<O>>(mut collection: C, o: O) -> C {
+    collection.extend(core::iter::once(o));
+    collection
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{combinators::is, parser::input::Input};
+    use crate::{
+        combinators::{decimal, is, maybe, one_of},
+        parser::{errors::ErrorKind, input::Input},
+    };
 
     #[test]
     fn can_parse_many_once() {
@@ -203,6 +479,91 @@ mod tests {
         assert_eq!(state.as_input(), &"123");
     }
 
+    #[test]
+    fn can_fold_many() {
+        let (state, parsed): (State<&str>, u32) = fold_many(
+            decimal.then(maybe(is(","))),
+            || 0,
+            |acc, (n, _): (Input<&str>, _)| acc + n.as_inner().parse::<u32>().unwrap(),
+        )
+        .process("1,20,300;".into())
+        .unwrap();
+        assert_eq!(parsed, 321);
+        assert_eq!(state.as_input(), &";");
+
+        let (_, parsed): (State<&str>, u32) = fold_many(is("x"), || 7, |acc, _| acc + 1)
+            .process("y".into())
+            .unwrap();
+        assert_eq!(parsed, 7);
+
+        let state: State<&str> = fold_many_n(2, is("x"), || 0, |acc, _| acc + 1)
+            .process("xy".into())
+            .unwrap_err();
+        assert!(state.is_err());
+
+        let (state, parsed): (State<&str>, u32) =
+            fold_many_m_n(0, 2, is("x"), || 0, |acc, _| acc + 1)
+                .process("xxx".into())
+                .unwrap();
+        assert_eq!(parsed, 2);
+        assert_eq!(state.as_input(), &"x");
+    }
+
+    #[test]
+    fn can_parse_many_into() {
+        let (_, parsed): (State<&str>, String) =
+            many_into(one_of("abc").map(|c: Input<&str>| c.as_inner().to_uppercase()))
+                .process("abcd".into())
+                .unwrap();
+        assert_eq!(parsed, "ABC");
+
+        let (_, parsed): (State<&str>, std::collections::BTreeSet<String>) = many_n_into(
+            1,
+            one_of("ab").map(|c: Input<&str>| c.as_inner().to_string()),
+        )
+        .process("abba".into())
+        .unwrap();
+        assert_eq!(parsed.len(), 2);
+
+        let (state, parsed): (State<&str>, Vec<Input<&str>>) =
+            many_m_n_into(1, 2, is("a")).process("aaa".into()).unwrap();
+        assert_eq!(parsed, ["a", "a"]);
+        assert_eq!(state.as_input(), &"a");
+    }
+
+    #[test]
+    fn many_stops_without_progress() {
+        let line = line!() + 1;
+        let parser = many(maybe(is("x")));
+        let state: State<&str> = parser.process("xxy".into()).unwrap_err();
+        assert_eq!(state.as_input(), &"y");
+
+        let location = match state.errors().kind() {
+            ErrorKind::NoProgress(location) => *location,
+            kind => panic!("expected a `NoProgress` error, not {:?}", kind),
+        };
+        if cfg!(debug_assertions) {
+            let location = location.unwrap();
+            assert_eq!(location.file(), file!());
+            assert_eq!(location.line(), line);
+        }
+        assert_eq!(state.errors().from(), Input::new_with_span("xxy", 2..2));
+        assert!(state
+            .errors()
+            .to_string()
+            .starts_with("repeated parser made no progress"));
+
+        let state: State<&str> = many_until(maybe(is("x")), is("!"))
+            .process("y".into())
+            .unwrap_err();
+        assert!(matches!(state.errors().kind(), ErrorKind::NoProgress(_)));
+
+        let state: State<&str> = fold_many_m_n(0, 10, maybe(is("x")), || (), |_, _| ())
+            .process("".into())
+            .unwrap_err();
+        assert!(matches!(state.errors().kind(), ErrorKind::NoProgress(_)));
+    }
+
     /*
         #[test]
         fn can_parse_many_n() {
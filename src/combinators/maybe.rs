@@ -6,16 +6,16 @@ use crate::parser::{errors::CustomError, input::Underlying, state::State, Parser
 /// # use errgonomic::combinators::{maybe, is};
 /// # use errgonomic::parser::Parser;
 /// # use errgonomic::parser::errors::DummyError;
-/// let parsed = maybe(is::<_, DummyError>("te")).parse("test").unwrap();
+/// let parsed = maybe(is::<_, DummyError, ()>("te")).parse("test").unwrap();
 /// assert_eq!(parsed.unwrap(), "te");
 ///
-/// let parsed = maybe(is::<_, DummyError>("st")).parse("test").unwrap();
+/// let parsed = maybe(is::<_, DummyError, ()>("st")).parse("test").unwrap();
 /// assert_eq!(parsed, None);
 /// ```
-pub fn maybe<I: Underlying, O, E: CustomError, P: Parser<I, O, E>>(
+pub fn maybe<I: Underlying, O, E: CustomError, C, P: Parser<I, O, E, C>>(
     p: P,
-) -> impl Parser<I, Option<O>, E> {
-    move |state: State<I, E>| match p.process(state.fork()) {
+) -> impl Parser<I, Option<O>, E, C> {
+    move |state: State<I, E, C>| match p.process(state.fork()) {
         Ok((new_state, o)) => Ok((new_state, Some(o))),
         Err(_) => Ok((state, None)),
     }
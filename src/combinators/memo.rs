@@ -0,0 +1,445 @@
+use crate::parser::{
+    errors::{CustomError, Error, ErrorKind, ExpectedError},
+    input::Underlying,
+    state::State,
+    Parser,
+};
+use core::panic::Location;
+use std::sync::Arc;
+
+/// Memoizes `p` (i.e. packrat parsing): the first time it's parsed at a position, its result is
+/// cached in the state, and it's reused whenever the parser backtracks to that position, for the
+/// rest of the parse. Memoizing every rule that's backtracked over avoids parsing it again, which
+/// can make a heavily backtracking grammar parse in linear time (see `State::memo_stats` for how
+/// often it helped).
+///
+/// The output is cloned out of the cache, so it should be cheap to clone (see `memo_arc`).
+///
+/// NOTE: A rule is identified by where `memo` is called, so the same rule can be built again
+/// (e.g. in a recursive function). However, this means a function that memoizes a parser it's
+/// given would memoize every parser as the same rule, so don't do that!
+///
+/// NOTE: A result is only reused from the same state it was parsed from, i.e. with the same errors,
+/// user-defined context and indentation, so that a hit never brings back a change that was
+/// backtracked. So, once errors have been recorded (e.g. by recovery), a rule reached with
+/// different errors is parsed again, and every lookup compares them, which costs as much as
+/// they're long. Memoization works best before any errors are recorded.
+///
+/// NOTE: As outputs are cached in the state, they can't borrow from the input. Use a span, or an
+/// owned value, instead.
+///
+/// ```
+/// # use errgonomic::combinators::{memo, any, is, decimal};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::{State, MemoStats};
+/// # use errgonomic::parser::errors::Result;
+/// # use errgonomic::parser::input::Input;
+/// // Both alternatives start with a (memoized) number, which is only parsed once
+/// fn number(state: State<&str>) -> Result<&str, u32> {
+///     memo(decimal.map(|n: Input<&str>| n.as_inner().parse().unwrap())).process(state)
+/// }
+///
+/// let parser = any((
+///     number.then(is("+")).then(number).map(|((a, _), b)| a + b),
+///     number,
+/// ));
+/// let (state, parsed): (State<&str>, u32) = parser.process("42".into()).unwrap();
+/// assert_eq!(parsed, 42);
+/// assert_eq!(state.memo_stats(), MemoStats { hits: 1, misses: 1 });
+/// ```
+#[track_caller]
+pub fn memo<I, O, E, C, P>(p: P) -> impl Parser<I, O, E, C>
+where
+    I: Underlying,
+    O: Clone + Send + 'static,
+    E: CustomError,
+    P: Parser<I, O, E, C>,
+{
+    memoize(Location::caller(), p)
+}
+
+/// Like `memo`, but for outputs that are expensive (or impossible) to clone, e.g. a syntax tree:
+/// the output is shared instead.
+///
+/// ```
+/// # use errgonomic::combinators::{memo_arc, is};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use std::sync::Arc;
+/// #[derive(Debug, PartialEq)]
+/// struct Tree(Vec<Tree>);
+///
+/// let parser = memo_arc(is("()").map(|_| Tree(vec![])));
+/// let (_, parsed): (State<&str>, _) = parser.process("()".into()).unwrap();
+/// assert_eq!(parsed, Arc::new(Tree(vec![])));
+/// ```
+#[track_caller]
+pub fn memo_arc<I, O, E, C, P>(p: P) -> impl Parser<I, Arc<O>, E, C>
+where
+    I: Underlying,
+    O: Send + Sync + 'static,
+    E: CustomError,
+    P: Parser<I, O, E, C>,
+{
+    memoize(Location::caller(), p.map(Arc::new))
+}
+
+/// INTERNAL: Memoizes `p` as `rule`.
+fn memoize<I, O, E, C, P>(rule: &'static Location<'static>, p: P) -> impl Parser<I, O, E, C>
+where
+    I: Underlying,
+    O: Clone + Send + 'static,
+    E: CustomError,
+    P: Parser<I, O, E, C>,
+{
+    move |state: State<I, E, C>| {
+        if let Some(result) = state.memo_lookup(rule) {
+            return result;
+        }
+
+        let start = state.fork();
+        let result = p.process(state);
+        match &result {
+            Ok((state, output)) => state.memo_insert(rule, &start, Some(output)),
+            Err(state) => state.memo_insert::<O>(rule, &start, None),
+        }
+        result
+    }
+}
+
+/// Parses `p`, a rule that may be left-recursive, i.e. start by (directly or indirectly) parsing
+/// itself, like `expr = expr "-" number | number`, which would otherwise recurse forever.
+///
+/// The rule is parsed by growing a seed: its first parse at a position fails when it reaches
+/// itself again, which leaves its other alternatives (`number`) as the seed. It's then parsed again
+/// with its result at that position memoized as the seed (`number "-" number`), and so on, for as
+/// long as the seed grows. Mutually left-recursive rules should all be `left_recursive`. If the
+/// seed can't grow any further because of a committed error, that error is returned instead.
+///
+/// Like `memo`, the result is memoized (so the output is cloned), and the rule is identified by
+/// where `left_recursive` is called. So, the rule can't change the state (e.g. the user-defined
+/// context) before reaching itself, or it would never find its seed.
+///
+/// ```
+/// # use errgonomic::combinators::{left_recursive, any, is, decimal};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::errors::Result;
+/// # use errgonomic::parser::input::Input;
+/// fn number(state: State<&str>) -> Result<&str, i64> {
+///     decimal.map(|n: Input<&str>| n.as_inner().parse().unwrap()).process(state)
+/// }
+///
+/// // Subtraction is left-associative
+/// fn expr(state: State<&str>) -> Result<&str, i64> {
+///     left_recursive(any((
+///         expr.then(is("-")).then(number).map(|((a, _), b)| a - b),
+///         number,
+///     )))
+///     .process(state)
+/// }
+///
+/// let (_, parsed) = expr.process("10-2-3".into()).unwrap();
+/// assert_eq!(parsed, 5);
+/// ```
+#[track_caller]
+pub fn left_recursive<I, O, E, C, P>(p: P) -> impl Parser<I, O, E, C>
+where
+    I: Underlying,
+    O: Clone + Send + 'static,
+    E: CustomError,
+    P: Parser<I, O, E, C>,
+{
+    let rule = Location::caller();
+    move |state: State<I, E, C>| {
+        if let Some(result) = state.memo_lookup(rule) {
+            return result;
+        }
+
+        // The rule fails whenever it reaches itself before the seed is planted
+        state
+            .fork()
+            .with_error(Error::new(
+                ErrorKind::expected(ExpectedError::LeftRecursion),
+                state.as_input().fork().take(0),
+            ))
+            .memo_insert::<O>(rule, &state, None);
+        let mut mark = state.memo_mark();
+        let mut seed = match p.process(state.fork()) {
+            Ok(seed) => seed,
+            Err(failed) => {
+                let failed = without_seed_errors(failed);
+                failed.memo_insert::<O>(rule, &state, None);
+                return Err(failed);
+            }
+        };
+
+        // Grow the seed, until it stops growing
+        loop {
+            // Everything memoized since the last seed was planted may depend on it
+            state.memo_forget(mark);
+            seed.0.memo_insert(rule, &state, Some(&seed.1));
+            mark = state.memo_mark();
+
+            match p.process(state.fork()) {
+                Ok(grown) if grown.0.as_input().span().head() > seed.0.as_input().span().head() => {
+                    seed = grown
+                }
+                Err(e) if e.errors().is_committed() => {
+                    e.memo_insert::<O>(rule, &state, None);
+                    return Err(e);
+                }
+                _ => return Ok(seed),
+            }
+        }
+    }
+}
+
+/// INTERNAL: Removes the failures of the seed before it was planted (see `left_recursive`) from
+/// the errors, as they aren't real errors, unless they're all there is (i.e. nothing else could
+/// have been the seed).
+fn without_seed_errors<I: Underlying, E: CustomError, C>(state: State<I, E, C>) -> State<I, E, C> {
+    let is_seed =
+        |e: &Error<I, E>| matches!(e.kind(), ErrorKind::Expected(ExpectedError::LeftRecursion));
+    let errors = state.errors().clone().without(&is_seed);
+    if errors.is_empty() {
+        return state;
+    }
+    state.map_errors(|_| errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        combinators::{
+            any, commit, decimal, identifier, is, update_context, whitespace_wrapped as ww,
+        },
+        parser::{
+            errors::{DummyError, Result},
+            input::Input,
+            state::MemoStats,
+        },
+    };
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn memoized_backtracking_is_linear() {
+        // `a` is either `(a)` or `(a]`, so without memoization, the innermost `a` is parsed once
+        // for every way to choose the outer ones, i.e. 2^n times
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        fn a(state: State<&str>) -> Result<&str, usize> {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+            memo(any((
+                is("(").then(a).then(is(")")).map(|((_, n), _)| n + 1),
+                is("(").then(a).then(is("]")).map(|((_, n), _)| n + 1),
+                is("x").map(|_| 0),
+            )))
+            .process(state)
+        }
+
+        let input = format!("{}x{}", "(".repeat(20), "]".repeat(20));
+        let (state, parsed) = a.process(State::new(input.as_str())).unwrap();
+        assert_eq!(parsed, 20);
+        assert!(CALLS.load(Ordering::Relaxed) < 100);
+        assert_eq!(
+            state.memo_stats(),
+            MemoStats {
+                hits: 20,
+                misses: 21
+            }
+        );
+    }
+
+    #[test]
+    fn failures_are_memoized() {
+        let one = || memo(is("1").map(|_: Input<&str>| ()));
+        let parser = any((
+            one().then(is("!")).map(|_| ()),
+            one().then(is("?")).map(|_| ()),
+            is("2").map(|_| ()),
+        ));
+
+        let (state, _): (State<&str>, _) = parser.process(State::new("2")).unwrap();
+        assert_eq!(state.memo_stats(), MemoStats { hits: 1, misses: 1 });
+
+        let state: State<&str> = parser.process(State::new("3")).unwrap_err();
+        assert_eq!(state.memo_stats(), MemoStats { hits: 1, misses: 1 });
+        assert_eq!(
+            state.errors().to_string(),
+            "expected `1`, or expected `1`, or expected `2`"
+        );
+    }
+
+    #[test]
+    fn hits_restore_the_state() {
+        // A hit consumes the same input, and leaves the same context, as parsing the rule did
+        let plus = || memo(update_context(is("+"), |n: &u32, _| n + 1).map(|_| ()));
+        let parser = any((
+            plus().then(plus()).then(is("!")).map(|_| ()),
+            plus().then(plus()).then(is("?")).map(|_| ()),
+        ));
+
+        let (state, _): (State<&str, DummyError, u32>, _) =
+            parser.process(State::new("++?")).unwrap();
+        assert_eq!(state.memo_stats(), MemoStats { hits: 2, misses: 2 });
+        assert_eq!(state.context(), Some(&2));
+        assert_eq!(state.as_input().as_inner(), "");
+
+        // Different rules at the same position are different
+        let parser = plus().then(memo(is("+").map(|_| 5u8)));
+        let (state, (_, parsed)): (State<&str, DummyError, u32>, _) =
+            parser.process(State::new("++")).unwrap();
+        assert_eq!(parsed, 5);
+        assert_eq!(state.memo_stats(), MemoStats { hits: 0, misses: 2 });
+    }
+
+    #[test]
+    fn backtracked_changes_are_not_reused() {
+        // The first alternative declares `x` before memoizing `y`, but it's backtracked, so the
+        // result of `y` from it can't be reused (or `x` would still be declared)
+        let name = || ww(identifier()).map(|n: Input<&'static str>| n.as_inner().to_string());
+        let declare = update_context(name(), |names: &Vec<String>, n| {
+            let mut names = names.clone();
+            names.push(n.clone());
+            names
+        });
+        let rule = || memo(name());
+        let parser = any((
+            declare.then(rule()).then(is("!")).map(|_| ()),
+            name().then(rule()).map(|_| ()),
+        ));
+
+        let (state, _): (State<&str, DummyError, Vec<String>>, _) =
+            parser.process(State::new("x y")).unwrap();
+        assert_eq!(state.context(), None);
+        assert_eq!(state.memo_stats(), MemoStats { hits: 0, misses: 2 });
+    }
+
+    fn number(state: State<&str>) -> Result<&str, i64> {
+        decimal
+            .map(|n: Input<&str>| n.as_inner().parse().unwrap())
+            .process(state)
+    }
+
+    #[test]
+    fn can_parse_direct_left_recursion() {
+        // expr = expr "+" term | term, term = term "*" number | number
+        fn expr(state: State<&str>) -> Result<&str, String> {
+            left_recursive(any((
+                expr.then(is("+"))
+                    .then(term)
+                    .map(|((a, _), b)| format!("({a}+{b})")),
+                term,
+            )))
+            .process(state)
+        }
+
+        fn term(state: State<&str>) -> Result<&str, String> {
+            left_recursive(any((
+                term.then(is("*"))
+                    .then(number)
+                    .map(|((a, _), b)| format!("({a}*{b})")),
+                number.map(|n| n.to_string()),
+            )))
+            .process(state)
+        }
+
+        let (state, parsed) = expr.process(State::new("1+2*3*4+5")).unwrap();
+        assert_eq!(parsed, "((1+((2*3)*4))+5)");
+        assert_eq!(state.as_input().as_inner(), "");
+
+        // The longest prefix is parsed
+        let (state, parsed) = expr.process(State::new("1+2+")).unwrap();
+        assert_eq!(parsed, "(1+2)");
+        assert_eq!(state.as_input().as_inner(), "+");
+
+        // Without a seed, the rule fails
+        let state = expr.process(State::new("+1")).unwrap_err();
+        assert_eq!(state.errors().to_string(), "expected a decimal digit");
+    }
+
+    #[test]
+    fn left_recursion_needs_a_seed() {
+        fn sum(state: State<&str>) -> Result<&str, i64> {
+            left_recursive(sum.then(is("+")).then(number).map(|((a, _), b)| a + b)).process(state)
+        }
+
+        let state = sum.process(State::new("1+2")).unwrap_err();
+        assert_eq!(
+            state.errors().to_string(),
+            "expected a non-left-recursive alternative"
+        );
+    }
+
+    #[test]
+    fn left_recursion_respects_commits() {
+        // expr = expr "+" commit(number) | number
+        fn expr(state: State<&str>) -> Result<&str, i64> {
+            left_recursive(any((
+                expr.then(is("+"))
+                    .then(commit(number))
+                    .map(|((a, _), b)| a + b),
+                number,
+            )))
+            .process(state)
+        }
+
+        let (_, parsed) = expr.process(State::new("1+2+3")).unwrap();
+        assert_eq!(parsed, 6);
+
+        let state = expr.process(State::new("1+2+x")).unwrap_err();
+        assert!(state.errors().is_committed());
+        assert_eq!(state.errors().to_string(), "expected a decimal digit");
+    }
+
+    #[test]
+    fn can_parse_indirect_left_recursion() {
+        // a = b "x" | "a", b = a "y" | "b"
+        fn a(state: State<&str>) -> Result<&str, String> {
+            left_recursive(any((
+                b.then(is("x")).map(|(b, _)| format!("({b}x)")),
+                is("a").map(|_| "a".to_string()),
+            )))
+            .process(state)
+        }
+
+        fn b(state: State<&str>) -> Result<&str, String> {
+            left_recursive(any((
+                a.then(is("y")).map(|(a, _)| format!("({a}y)")),
+                is("b").map(|_| "b".to_string()),
+            )))
+            .process(state)
+        }
+
+        let (state, parsed) = a.process(State::new("ayxyx")).unwrap();
+        assert_eq!(parsed, "((((ay)x)y)x)");
+        assert_eq!(state.as_input().as_inner(), "");
+
+        let (_, parsed) = a.process(State::new("bxyx")).unwrap();
+        assert_eq!(parsed, "(((bx)y)x)");
+
+        let (_, parsed) = b.process(State::new("ayxy")).unwrap();
+        assert_eq!(parsed, "(((ay)x)y)");
+
+        // Through a rule that isn't left-recursive itself
+        fn list(state: State<&str>) -> Result<&str, Vec<i64>> {
+            left_recursive(any((
+                items.then(is(",")).then(number).map(|((mut items, _), n)| {
+                    items.push(n);
+                    items
+                }),
+                number.map(|n| vec![n]),
+            )))
+            .process(state)
+        }
+
+        fn items(state: State<&str>) -> Result<&str, Vec<i64>> {
+            list.process(state)
+        }
+
+        let (_, parsed) = list.process(State::new("1,2,3")).unwrap();
+        assert_eq!(parsed, [1, 2, 3]);
+    }
+}
@@ -9,31 +9,66 @@
 mod alphabetic;
 mod any;
 mod between;
+mod class;
 mod compare;
 mod consumed;
+mod context;
+mod dispatch;
 mod eoi;
+mod float;
+mod glyph;
 mod id;
+mod identifier;
+mod indent;
+mod integer;
+mod keyword;
+mod literals;
+mod lookahead;
 mod many;
 mod maybe;
+mod memo;
 mod numeric;
+mod permutation;
 mod pratt;
 mod recovery;
 mod separated;
+mod seq;
+mod string;
 mod take;
+mod trivia;
+#[cfg(feature = "unicode")]
+mod unicode;
 mod whitespace;
 
 pub use alphabetic::*;
 pub use any::*;
 pub use between::*;
+pub use class::*;
 pub use compare::*;
 pub use consumed::*;
+pub use context::*;
+pub use dispatch::*;
 pub use eoi::*;
+pub use float::*;
 pub use id::*;
+pub use identifier::*;
+pub use indent::*;
+pub use integer::*;
+pub use keyword::*;
+pub use literals::*;
+pub use lookahead::*;
 pub use many::*;
 pub use maybe::*;
+pub use memo::*;
 pub use numeric::*;
+pub use permutation::*;
 pub use pratt::*;
 pub use recovery::*;
 pub use separated::*;
+pub use seq::*;
+pub use string::*;
 pub use take::*;
+pub use trivia::*;
+#[cfg(feature = "unicode")]
+pub use unicode::*;
 pub use whitespace::*;
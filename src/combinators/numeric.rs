@@ -1,13 +1,16 @@
 use crate::parser::{
-    errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
+    errors::{CustomError, ExpectedError, Result},
     input::{Input, Underlying},
     state::State,
-    Parser,
 };
 
-use super::many_n;
+use super::glyph::{glyph, glyphs};
 
 /// Parses a decimal digit until it stops. If there is no decimal digit, returns an error.
+///
+/// NOTE: Only matches ASCII digits, even with the `unicode` feature enabled, so that the output
+/// can always be handed to `str::parse`. For any Unicode decimal digit (`Nd`), use
+/// `unicode_digit` instead.
 ///```
 /// # use errgonomic::combinators::decimal_digit;
 /// # use errgonomic::parser::Parser;
@@ -17,18 +20,10 @@ use super::many_n;
 /// assert_eq!(parsed, "1");
 /// assert_eq!(state.as_input().as_inner(), "23abc");
 ///```
-pub fn decimal_digit<I: Underlying, E: CustomError>(state: State<I, E>) -> Result<I, Input<I>, E> {
-    let input = state.as_input().fork();
-    match input.peek() {
-        Some(c) if c.is_ascii_digit() => {
-            let num = input.take(1);
-            Ok((state.with_input(input.skip(1)), num))
-        }
-        _ => Err(state.with_error(Error::new(
-            ErrorKind::expected(ExpectedError::Digit(10)),
-            input.take(1),
-        ))),
-    }
+pub fn decimal_digit<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyph(state, |c| c.is_ascii_digit(), ExpectedError::Digit(10))
 }
 
 /// Parses a decimal number until it stops. If there is no decimal number, returns an error.
@@ -41,14 +36,10 @@ pub fn decimal_digit<I: Underlying, E: CustomError>(state: State<I, E>) -> Resul
 /// assert_eq!(parsed, "123");
 /// assert_eq!(state.as_input().as_inner(), "abc");
 ///```
-pub fn decimal<I: Underlying, E: CustomError>(state: State<I, E>) -> Result<I, Input<I>, E> {
-    many_n(1, decimal_digit)
-        .map(|xs| {
-            xs.into_iter()
-                .reduce(|acc, x| acc.join(&x))
-                .expect("to have parsed at least one digit!")
-        })
-        .process(state)
+pub fn decimal<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyphs(state, |c| c.is_ascii_digit(), ExpectedError::Digit(10))
 }
 
 /// Parses a hexadecimal digit until it stops. If there is no hexadecimal digit, returns an error.
@@ -61,20 +52,10 @@ pub fn decimal<I: Underlying, E: CustomError>(state: State<I, E>) -> Result<I, I
 /// assert_eq!(parsed, "1");
 /// assert_eq!(state.as_input().as_inner(), "23abcdefghi");
 ///```
-pub fn hexadecimal_digit<I: Underlying, E: CustomError>(
-    state: State<I, E>,
-) -> Result<I, Input<I>, E> {
-    let input = state.as_input().fork();
-    match state.as_input().peek() {
-        Some(c) if c.is_ascii_hexdigit() => {
-            let num = input.fork().take(1);
-            Ok((state.with_input(input.skip(1)), num))
-        }
-        _ => Err(state.with_error(Error::new(
-            ErrorKind::expected(ExpectedError::Digit(16)),
-            input.take(1),
-        ))),
-    }
+pub fn hexadecimal_digit<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyph(state, |c| c.is_ascii_hexdigit(), ExpectedError::Digit(16))
 }
 
 /// Parses a hexadecimal number until it stops. If there is no hexadecimal number, returns an
@@ -88,20 +69,19 @@ pub fn hexadecimal_digit<I: Underlying, E: CustomError>(
 /// assert_eq!(parsed, "123abcdef");
 /// assert_eq!(state.as_input().as_inner(), "ghi");
 ///```
-pub fn hexadecimal<I: Underlying, E: CustomError>(state: State<I, E>) -> Result<I, Input<I>, E> {
-    many_n(1, hexadecimal_digit)
-        .map(|xs| {
-            xs.into_iter()
-                .reduce(|acc, x| acc.join(&x))
-                .expect("to have parsed at least one digit!")
-        })
-        .process(state)
+pub fn hexadecimal<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyphs(state, |c| c.is_ascii_hexdigit(), ExpectedError::Digit(16))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::Parser;
+    use crate::parser::{
+        errors::{Error, ErrorKind},
+        Parser,
+    };
 
     #[test]
     fn can_parse_dec_digit() {
@@ -211,4 +191,23 @@ mod tests {
             )
         );
     }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn can_parse_decimals_with_unicode() {
+        let (state, parsed): (State<&str>, Input<&str>) = decimal.process("12٣".into()).unwrap();
+        assert_eq!(parsed, "12");
+        assert_eq!(state.as_input(), &"٣");
+        assert!(!state.is_err());
+
+        // Errors span the whole character, not just its first byte
+        let state: State<&str> = decimal_digit.process("٣".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Digit(10)),
+                Input::new_with_span("٣", 0..2)
+            )
+        );
+    }
 }
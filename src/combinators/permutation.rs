@@ -0,0 +1,292 @@
+use crate::parser::{
+    errors::{CustomError, Error, ErrorKind, Result},
+    input::{Input, Underlying},
+    state::State,
+    Parser,
+};
+use eval_macro::eval;
+
+/// Parses all of the given parsers exactly once, but in any order, and returns all of their
+/// outputs as a flat tuple in the order the parsers were given (*not* the order they matched in).
+/// Wrap a parser in `optional` to allow it to be missing.
+///
+/// If a parser matches more than once before the others have, a `Duplicate` error is returned over
+/// the second match. If a parser doesn't match at all, a `Missing` error is returned (for each of
+/// them) with the error it gave at the end of the permutation. The first parser that can match at
+/// each point is used, so parsers shouldn't overlap.
+///
+/// NOTE: Only matches that consume input count, so parsers that can match nothing (like
+/// `optional`) are only used at the end, if they didn't match anything before. Up to 20 parsers are
+/// supported.
+///
+///```
+/// # use errgonomic::combinators::{permutation, optional, is};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// let flags = permutation((is("-a"), is("-b"), optional(is("-c"))));
+///
+/// let (state, (a, b, c)): (State<&str>, (Input<&str>, Input<&str>, Option<Input<&str>>)) =
+///     flags.process("-b-a!".into()).unwrap();
+/// assert_eq!(a, "-a");
+/// assert_eq!(b, "-b");
+/// assert!(c.is_none());
+/// assert_eq!(state.as_input().as_inner(), "!");
+///
+/// let state: State<&str> = flags.process("-a-c-a".into()).unwrap_err();
+/// assert_eq!(state.errors().to_string(), "duplicate `-a`");
+///```
+#[inline]
+#[allow(private_bounds)]
+pub fn permutation<I: Underlying, O, E: CustomError, C, L: Permutation<I, O, E, C>>(
+    ps: L,
+) -> impl Parser<I, O, E, C> {
+    move |state| ps.permutation(state)
+}
+
+/// Parses `p` if it can, returning `None` (without consuming anything) if it can't. Meant for
+/// elements of a `permutation` that may be missing.
+///
+/// NOTE: Unlike `maybe`, committed errors are still returned.
+///
+///```
+/// # use errgonomic::combinators::{optional, is};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::errors::DummyError;
+/// assert_eq!(optional(is::<_, DummyError, ()>("x")).parse("y").unwrap(), None);
+///```
+pub fn optional<I: Underlying, O, E: CustomError, C, P: Parser<I, O, E, C>>(
+    p: P,
+) -> impl Parser<I, Option<O>, E, C> {
+    move |state: State<I, E, C>| match p.process(state.fork()) {
+        Ok((new_state, o)) => Ok((new_state, Some(o))),
+        Err(e) if e.errors().is_committed() => Err(e),
+        Err(_) => Ok((state, None)),
+    }
+}
+
+/* TRAIT IMPLEMENTATIONS NEEDED FOR PERMUTATION */
+/* These are annoying and long, you can ignore*/
+
+trait Permutation<I: Underlying, O, E: CustomError, C> {
+    fn permutation(&self, state: State<I, E, C>) -> Result<I, O, E, C>;
+}
+
+// Magic macro magic that makes the impl. of `Permutation` for (nearly!) all tuples of parsers.
+// See: https://crates.io/crates/eval-macro
+eval! {
+    const UP_TO: usize = 20; // NOTE: The maximum size of the parser-tuples we want to implement.
+
+    for n in 1..=UP_TO {
+        let parser_generics = (1..=n)
+            .map(|i| format!("P{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let output_generics = (1..=n)
+            .map(|i| format!("O{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        // NOTE: Leading comma so that this also works for the single-tuple
+        let parser_tuple = format!("({parser_generics},)");
+        let output_tuple = format!("({output_generics},)");
+        let parser_defs = (1..=n)
+            .map(|i| format!("P{i}: Parser<I, O{i}, E, C>"))
+            .collect::<Vec<_>>()
+            .join(",\n");
+        let slots = (0..n)
+            .map(|i| format!("let mut o{i} = None;"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        // Try every parser that hasn't matched yet, starting over after each match.
+        let unmatched = (0..n)
+            .map(|i| format!("if o{i}.is_none() {{
+                match self.{i}.process(state.fork()) {{
+                    Ok((new_state, o)) if !consumed(&state, &new_state).is_empty() => {{
+                        o{i} = Some(o);
+                        state = new_state;
+                        continue;
+                    }}
+                    Err(e) if e.errors().is_committed() => return Err(e),
+                    Err(_) => incomplete = true,
+                    _ => {{}}
+                }}
+            }}"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        // If nothing else matches (and something still has to), but a parser that already matched
+        // does, it's a duplicate.
+        let matched = (0..n)
+            .map(|i| format!("if o{i}.is_some() {{
+                if let Ok((new_state, _)) = self.{i}.process(state.fork()) {{
+                    let found = consumed(&state, &new_state);
+                    if !found.is_empty() {{
+                        return Err(original.with_error(Error::new(
+                            ErrorKind::duplicate(found.as_inner()),
+                            found,
+                        )));
+                    }}
+                }}
+            }}"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        // Anything left can still match nothing, otherwise it's missing.
+        let finishing = (0..n)
+            .map(|i| format!("if o{i}.is_none() {{
+                match self.{i}.process(state.fork()) {{
+                    Ok((new_state, o)) => {{
+                        o{i} = Some(o);
+                        state = new_state;
+                    }}
+                    Err(e) if e.errors().is_committed() => return Err(e),
+                    Err(e) => {{
+                        let error = e.errors().clone();
+                        let from = error.from();
+                        missing.push(Error::new(ErrorKind::missing(error), from));
+                    }}
+                }}
+            }}"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let outputs = (0..n)
+            .map(|i| format!("o{i}.expect(\"to not be missing\"),"))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        output! {
+            impl<I, E, C, {{output_generics}}, {{parser_generics}}> Permutation<I, {{output_tuple}}, E, C> for {{parser_tuple}}
+            where
+                I: Underlying,
+                E: CustomError,
+                {{parser_defs}}
+            {
+                #[inline]
+                fn permutation(&self, state: State<I, E, C>) -> Result<I, {{output_tuple}}, E, C> {
+                    let original = state.fork();
+                    let mut state = state;
+                    {{slots}}
+
+                    loop {
+                        let mut incomplete = false;
+                        {{unmatched}}
+
+                        if incomplete {
+                            {{matched}}
+                        }
+
+                        break;
+                    }
+
+                    let mut missing: Vec<Error<I, E>> = vec![];
+                    {{finishing}}
+
+                    if !missing.is_empty() {
+                        return Err(missing.into_iter().fold(original, |state, e| state.with_error(e)));
+                    }
+
+                    Ok((state, ({{outputs}})))
+                }
+            }
+        }
+    }
+}
+
+/// INTERNAL: The input consumed going from `before` to `after`.
+fn consumed<I: Underlying, E: CustomError, C>(
+    before: &State<I, E, C>,
+    after: &State<I, E, C>,
+) -> Input<I> {
+    before.as_input().fork().subtract(after.as_input())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        combinators::{is, whitespace_wrapped as ww},
+        parser::errors::{DummyError, ExpectedError},
+    };
+
+    #[test]
+    fn can_parse_permutation() {
+        let parser = permutation((is("a"), is("b"), is("c")));
+
+        for input in ["abc", "acb", "bac", "bca", "cab", "cba"] {
+            let (state, (a, b, c)): (State<&str>, _) = parser.process(input.into()).unwrap();
+            assert_eq!(a, "a");
+            assert_eq!(b, "b");
+            assert_eq!(c, "c");
+            assert!(state.as_input().is_empty());
+            assert!(!state.is_err());
+        }
+
+        // Only matches each once
+        let (state, _): (State<&str>, _) = permutation((is("a"), is("b")))
+            .process("bad".into())
+            .unwrap();
+        assert_eq!(state.as_input(), &"d");
+    }
+
+    #[test]
+    fn can_parse_optional_in_permutation() {
+        let parser = permutation((ww(is("x")), optional(ww(is("y"))), ww(is("z"))));
+
+        let (_, (x, y, z)): (State<&str>, _) = parser.process(" z x ".into()).unwrap();
+        assert_eq!(x, "x");
+        assert!(y.is_none());
+        assert_eq!(z, "z");
+
+        let (_, (_, y, _)): (State<&str>, _) = parser.process("y z x".into()).unwrap();
+        assert_eq!(y.unwrap(), "y");
+    }
+
+    #[test]
+    fn permutation_reports_duplicates() {
+        let state: State<&str> = permutation((is("a"), is("b"), is("c")))
+            .process("bab".into())
+            .unwrap_err();
+        assert_eq!(state.as_input(), &"bab");
+        assert_eq!(
+            state.errors(),
+            &Error::new(ErrorKind::duplicate("b"), Input::new_with_span("bab", 2..3))
+        );
+        assert_eq!(state.errors().to_string(), "duplicate `b`");
+    }
+
+    #[test]
+    fn permutation_can_be_followed_by_its_elements() {
+        // Once every element has matched, what follows isn't a duplicate
+        let (state, (_, a)): (State<&str>, _) = permutation((is("a"), is("b")))
+            .then(is("a"))
+            .process("aba".into())
+            .unwrap();
+        assert_eq!(a, "a");
+        assert!(state.as_input().is_empty());
+
+        // Including when the rest can match nothing
+        let flags = permutation((is("-a"), is("-b"), optional(is("-c"))));
+        let (state, (_, a)): (State<&str>, _) =
+            flags.then(is("-a")).process("-b-a-a".into()).unwrap();
+        assert_eq!(a, "-a");
+        assert!(state.as_input().is_empty());
+    }
+
+    #[test]
+    fn permutation_reports_missing() {
+        let state: State<&str> = permutation((is("a"), is("b"), optional(is("c")), is("d")))
+            .process("b!".into())
+            .unwrap_err();
+        assert_eq!(state.as_input(), &"b!");
+
+        let missing = |s, span| {
+            let error = Error::new(
+                ErrorKind::expected(ExpectedError::Is(s)),
+                Input::new_with_span("b!", span),
+            );
+            Error::<_, DummyError>::new(ErrorKind::missing(error.clone()), error.from())
+        };
+        let mut expected = missing("a", 1..2);
+        expected.push(missing("d", 1..2));
+        assert_eq!(state.errors(), &expected);
+        assert_eq!(state.errors().to_string(), "missing `a`\nmissing `d`");
+    }
+}
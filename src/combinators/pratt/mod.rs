@@ -27,25 +27,25 @@ pub enum Associativity {
     Right,
 }
 
-type PAtom<'a, I, OExpr, E> = &'a dyn Parser<I, OExpr, E>;
+type PAtom<'a, I, OExpr, E, C> = &'a dyn Parser<I, OExpr, E, C>;
 type CPrefix<OExpr, OOp, E> = fn(OOp, OExpr) -> std::result::Result<OExpr, E>;
 type CInfix<OExpr, OOp, E> = fn(OExpr, OOp, OExpr) -> std::result::Result<OExpr, E>;
 type CPostfix<OExpr, OOp, E> = fn(OExpr, OOp) -> std::result::Result<OExpr, E>;
 
 /// A pratt parser, which can handle parsing "operations" in expressions, like addition and
 /// multiplication, or really anything you can think up that is "expression-like".
-pub struct Pratt<'a, I, OExpr, OOp, E>
+pub struct Pratt<'a, I, OExpr, OOp, E, C = ()>
 //, CPrefix, CInfix, CPostfix>
 where
     I: Underlying,
     E: CustomError,
-    // PAtom: Parser<I, OExpr, E>,
+    // PAtom: Parser<I, OExpr, E, C>,
     // CPrefix: Fn(OOp, OExpr) -> std::result::Result<OExpr, E>,
     //CInfix: Fn(OExpr, OOp, OExpr) -> std::result::Result<OExpr, E>,
     // CPostfix: Fn(OExpr, OOp) -> std::result::Result<OExpr, E>,
 {
     /// The atomic parser.
-    pa: PAtom<'a, I, OExpr, E>,
+    pa: PAtom<'a, I, OExpr, E, C>,
 
     /// The prefix combinator
     cons_prefix: CPrefix<OExpr, OOp, E>,
@@ -57,18 +57,18 @@ where
     cons_postfix: CPostfix<OExpr, OOp, E>,
 
     /// The prefix operators
-    prefix_ops: Vec<PrefixOperator<'a, I, OOp, E>>,
+    prefix_ops: Vec<PrefixOperator<'a, I, OOp, E, C>>,
 
     /// The infix operators
-    infix_ops: Vec<InfixOperator<'a, I, OOp, E>>,
+    infix_ops: Vec<InfixOperator<'a, I, OOp, E, C>>,
 
     /// The postfix operators
-    postfix_ops: Vec<PostfixOperator<'a, I, OOp, E>>,
+    postfix_ops: Vec<PostfixOperator<'a, I, OOp, E, C>>,
 
     _marker: std::marker::PhantomData<(I, OExpr, OOp, E)>,
 }
 
-impl<'a, I, OExpr, OOp, E> Pratt<'a, I, OExpr, OOp, E>
+impl<'a, I, OExpr, OOp, E, C> Pratt<'a, I, OExpr, OOp, E, C>
 where
     I: Underlying,
     E: CustomError,
@@ -83,7 +83,7 @@ where
     /// NOTE: If you don't plan on using one of the `cons_*` functions, you can always just use a
     /// closure that returns an `unreachable!()`.
     pub fn new(
-        pa: PAtom<'a, I, OExpr, E>,
+        pa: PAtom<'a, I, OExpr, E, C>,
         cons_prefix: CPrefix<OExpr, OOp, E>,
         cons_infix: CInfix<OExpr, OOp, E>,
         cons_postfix: CPostfix<OExpr, OOp, E>,
@@ -116,7 +116,11 @@ where
     ///     .with_infix_op(is("*"), Associativity::Left)
     ///     .with_infix_op(is("+"), Associativity::Left);
     /// ```
-    pub fn with_infix_op<P: Parser<I, OOp, E> + 'a>(mut self, p: P, assoc: Associativity) -> Self {
+    pub fn with_infix_op<P: Parser<I, OOp, E, C> + 'a>(
+        mut self,
+        p: P,
+        assoc: Associativity,
+    ) -> Self {
         let (lbp, rbp) = match assoc {
             Associativity::Left => (1, 2),
             Associativity::Right => (2, 1),
@@ -140,7 +144,7 @@ where
     /// the operators affects their precedence. Notably, if you want precedence over other
     /// operators (including infix ones!), you would put the `with_prefix_op` call before the
     /// others.
-    pub fn with_prefix_op<P: Parser<I, OOp, E> + 'a>(mut self, p: P) -> Self {
+    pub fn with_prefix_op<P: Parser<I, OOp, E, C> + 'a>(mut self, p: P) -> Self {
         self.increment_precedence();
 
         self.prefix_ops.push(PrefixOperator {
@@ -155,7 +159,7 @@ where
     /// the operators affects their precedence. Notably, if you want precedence over other
     /// operators (including infix ones!), you would put the `with_postfix_op` call before the
     /// others.
-    pub fn with_postfix_op<P: Parser<I, OOp, E> + 'a>(mut self, p: P) -> Self {
+    pub fn with_postfix_op<P: Parser<I, OOp, E, C> + 'a>(mut self, p: P) -> Self {
         self.increment_precedence();
 
         self.postfix_ops.push(PostfixOperator {
@@ -167,24 +171,24 @@ where
     }
 }
 
-impl<I, OExpr, OOp, E> Parser<I, OExpr, E> for Pratt<'_, I, OExpr, OOp, E>
+impl<I, OExpr, OOp, E, C> Parser<I, OExpr, E, C> for Pratt<'_, I, OExpr, OOp, E, C>
 where
     I: Underlying,
     E: CustomError,
 {
-    fn process(&self, state: State<I, E>) -> Result<I, OExpr, E> {
+    fn process(&self, state: State<I, E, C>) -> Result<I, OExpr, E, C> {
         self.pratt(state, usize::MIN)
     }
 }
 
-impl<I, OExpr, OOp, E> Pratt<'_, I, OExpr, OOp, E>
+impl<I, OExpr, OOp, E, C> Pratt<'_, I, OExpr, OOp, E, C>
 where
     I: Underlying,
     E: CustomError,
 {
     /// The actual pratt parser
-    fn pratt(&self, state: State<I, E>, min_lbp: usize) -> Result<I, OExpr, E> {
-        let (mut state, mut lhs): (State<I, E>, OExpr) = {
+    fn pratt(&self, state: State<I, E, C>, min_lbp: usize) -> Result<I, OExpr, E, C> {
+        let (mut state, mut lhs): (State<I, E, C>, OExpr) = {
             // try processing prefix
             // NOTE: Have to extract this expr. outside of the match b/c otherwise Rust doesn't
             // drop `self.prefix_ops` until end of match, causing a multiple-mutable-borrow error.
@@ -241,12 +245,15 @@ where
     }
 
     /// Parses first infix operator that works.
-    fn parse_infix_op(&self, state: State<I, E>) -> Result<I, (usize, OOp, usize), E> {
+    fn parse_infix_op(&self, state: State<I, E, C>) -> Result<I, (usize, OOp, usize), E, C> {
         any(&self.infix_ops).process(state)
     }
 
     /// Parses first prefix operator that works.
-    fn maybe_parse_prefix_op(&self, state: State<I, E>) -> Result<I, Option<(OOp, usize)>, E> {
+    fn maybe_parse_prefix_op(
+        &self,
+        state: State<I, E, C>,
+    ) -> Result<I, Option<(OOp, usize)>, E, C> {
         if self.prefix_ops.is_empty() {
             return Ok((state, None));
         }
@@ -255,7 +262,10 @@ where
     }
 
     /// Parses first postfix operator that works.
-    fn maybe_parse_postfix_op(&self, state: State<I, E>) -> Result<I, Option<(usize, OOp)>, E> {
+    fn maybe_parse_postfix_op(
+        &self,
+        state: State<I, E, C>,
+    ) -> Result<I, Option<(usize, OOp)>, E, C> {
         if self.postfix_ops.is_empty() {
             return Ok((state, None));
         }
@@ -5,13 +5,13 @@ use crate::parser::{
     Parser,
 };
 
-pub struct PrefixOperator<'a, I, O, E>
+pub struct PrefixOperator<'a, I, O, E, C = ()>
 where
     I: Underlying,
     E: CustomError,
 {
     // The operator parser
-    pub p: Box<dyn Parser<I, O, E> + 'a>,
+    pub p: Box<dyn Parser<I, O, E, C> + 'a>,
 
     /// The right-precedence of the operator
     pub rbp: usize,
@@ -19,7 +19,7 @@ where
     pub _marker: std::marker::PhantomData<(I, O, E)>,
 }
 
-impl<I, O, E> Parser<I, (O, usize), E> for PrefixOperator<'_, I, O, E>
+impl<I, O, E, C> Parser<I, (O, usize), E, C> for PrefixOperator<'_, I, O, E, C>
 where
     I: Underlying,
     E: CustomError,
@@ -27,20 +27,20 @@ where
     /// Returns, in order:
     ///     1. The operator,
     ///     2. the right binding power
-    fn process(&self, state: State<I, E>) -> Result<I, (O, usize), E> {
+    fn process(&self, state: State<I, E, C>) -> Result<I, (O, usize), E, C> {
         // NOTE: For some reason, I need to map *after* the parse, otherwise Rust gives a "move
         // occurs because `self.p` has type `P`, which does not implement the `Copy` trait" error.
         self.p.process(state).map(|(s, o)| (s, (o, self.rbp)))
     }
 }
 
-pub struct InfixOperator<'a, I, O, E>
+pub struct InfixOperator<'a, I, O, E, C = ()>
 where
     I: Underlying,
     E: CustomError,
 {
     // The operator parser
-    pub p: Box<dyn Parser<I, O, E> + 'a>,
+    pub p: Box<dyn Parser<I, O, E, C> + 'a>,
 
     /// The left-precedence of the operator
     pub lbp: usize,
@@ -51,7 +51,7 @@ where
     pub _marker: std::marker::PhantomData<(I, O, E)>,
 }
 
-impl<I, O, E> Parser<I, (usize, O, usize), E> for InfixOperator<'_, I, O, E>
+impl<I, O, E, C> Parser<I, (usize, O, usize), E, C> for InfixOperator<'_, I, O, E, C>
 where
     I: Underlying,
     E: CustomError,
@@ -60,7 +60,7 @@ where
     ///     1. The operator,
     ///     2. the left binding power
     ///     3. the right binding power
-    fn process(&self, state: State<I, E>) -> Result<I, (usize, O, usize), E> {
+    fn process(&self, state: State<I, E, C>) -> Result<I, (usize, O, usize), E, C> {
         // NOTE: For some reason, I need to map *after* the parse, otherwise Rust gives a "move
         // occurs because `self.p` has type `P`, which does not implement the `Copy` trait" error.
         self.p
@@ -69,13 +69,13 @@ where
     }
 }
 
-pub struct PostfixOperator<'a, I, O, E>
+pub struct PostfixOperator<'a, I, O, E, C = ()>
 where
     I: Underlying,
     E: CustomError,
 {
     // The operator parser
-    pub p: Box<dyn Parser<I, O, E> + 'a>,
+    pub p: Box<dyn Parser<I, O, E, C> + 'a>,
 
     /// The left-precedence of the operator
     pub lbp: usize,
@@ -83,7 +83,7 @@ where
     pub _marker: std::marker::PhantomData<(I, O, E)>,
 }
 
-impl<I, O, E> Parser<I, (usize, O), E> for PostfixOperator<'_, I, O, E>
+impl<I, O, E, C> Parser<I, (usize, O), E, C> for PostfixOperator<'_, I, O, E, C>
 where
     I: Underlying,
     E: CustomError,
@@ -91,7 +91,7 @@ where
     /// Returns, in order:
     ///     1. The operator,
     ///     2. the left binding power
-    fn process(&self, state: State<I, E>) -> Result<I, (usize, O), E> {
+    fn process(&self, state: State<I, E, C>) -> Result<I, (usize, O), E, C> {
         // NOTE: For some reason, I need to map *after* the parse, otherwise Rust gives a "move
         // occurs because `self.p` has type `P`, which does not implement the `Copy` trait" error.
         self.p.process(state).map(|(s, o)| (s, (self.lbp, o)))
@@ -5,13 +5,14 @@ pub fn prefix_cons_wrapper<
     OOp,
     OExpr,
     E: CustomError,
+    C,
     F: FnMut(OOp, OExpr) -> std::result::Result<OExpr, E>,
 >(
     op: OOp,
     rhs: OExpr,
     mut f: F,
-    state: State<I, E>,
-) -> Result<I, OExpr, E> {
+    state: State<I, E, C>,
+) -> Result<I, OExpr, E, C> {
     f(op, rhs).map(|x| (state.fork(), x)).map_err(|e| {
         let location = state.as_input().fork();
         state.with_error(Error::new(ErrorKind::custom(e), location))
@@ -23,14 +24,15 @@ pub fn infix_cons_wrapper<
     OOp,
     OExpr,
     E: CustomError,
+    C,
     F: FnMut(OExpr, OOp, OExpr) -> std::result::Result<OExpr, E>,
 >(
     lhs: OExpr,
     op: OOp,
     rhs: OExpr,
     mut f: F,
-    state: State<I, E>,
-) -> Result<I, OExpr, E> {
+    state: State<I, E, C>,
+) -> Result<I, OExpr, E, C> {
     f(lhs, op, rhs).map(|x| (state.fork(), x)).map_err(|e| {
         let location = state.as_input().fork();
         state.with_error(Error::new(ErrorKind::custom(e), location))
@@ -42,13 +44,14 @@ pub fn postfix_cons_wrapper<
     OOp,
     OExpr,
     E: CustomError,
+    C,
     F: FnMut(OExpr, OOp) -> std::result::Result<OExpr, E>,
 >(
     lhs: OExpr,
     op: OOp,
     mut f: F,
-    state: State<I, E>,
-) -> Result<I, OExpr, E> {
+    state: State<I, E, C>,
+) -> Result<I, OExpr, E, C> {
     f(lhs, op).map(|x| (state.fork(), x)).map_err(|e| {
         let location = state.as_input().fork();
         state.with_error(Error::new(ErrorKind::custom(e), location))
@@ -30,14 +30,14 @@ use super::take_until;
 /// assert!(state.is_err());
 /// ```
 #[inline]
-pub fn panic_recover<I, O1, O2, E, P1, P2>(p: P1, until: P2) -> impl Parser<I, Option<O1>, E>
+pub fn panic_recover<I, O1, O2, E, C, P1, P2>(p: P1, until: P2) -> impl Parser<I, Option<O1>, E, C>
 where
     I: Underlying,
     E: CustomError,
-    P1: Parser<I, O1, E>,
-    P2: Parser<I, O2, E>,
+    P1: Parser<I, O1, E, C>,
+    P2: Parser<I, O2, E, C>,
 {
-    move |state: State<I, E>| -> Result<I, Option<O1>, E> {
+    move |state: State<I, E, C>| -> Result<I, Option<O1>, E, C> {
         match p.process(state) {
             Ok((state, o)) => Ok((state, Some(o))),
             Err(state) => match take_until(|s| until.process(s)).process(state) {
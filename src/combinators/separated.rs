@@ -1,9 +1,11 @@
+use super::many::{caller, extend, no_progress, progressed};
 use crate::parser::{
-    errors::{CustomError, Result},
+    errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
     input::Underlying,
     state::State,
     Parser,
 };
+use core::panic::Location;
 
 /// Parses as many of the given parser as possible, separated by `sep`. At the first error, returns
 /// all the parsed output that happened before the error. If it errors out on the first parser, it
@@ -13,6 +15,12 @@ use crate::parser::{
 /// the input, *and consume them*. If it is false, then it will return an error if there are any
 /// dangling separators.
 ///
+/// NOTE: For minimum and maximum counts, requiring trailing separators, or keeping the separators,
+/// use `separated_list` instead.
+///
+/// If a separator and element together don't consume anything, a `NoProgress` error is returned
+/// instead of looping forever.
+///
 ///```
 /// # use errgonomic::combinators::{many, is, separated};
 /// # use errgonomic::parser::Parser;
@@ -23,20 +31,52 @@ use crate::parser::{
 /// assert_eq!(parsed, vec!["hello", "hello", "hello"]);
 /// assert_eq!(state.as_input().as_inner(), " world!");
 ///```
+#[track_caller]
 pub fn separated<
     I: Underlying,
     O1,
     O2,
     E: CustomError,
-    P1: Parser<I, O1, E>,
-    P2: Parser<I, O2, E>,
+    C,
+    P1: Parser<I, O1, E, C>,
+    P2: Parser<I, O2, E, C>,
 >(
     p: P1,
     sep: P2,
     ignore_dangling: bool,
-) -> impl Parser<I, Vec<O1>, E> {
-    move |state: State<I, E>| -> Result<I, Vec<O1>, E> {
-        let mut results = Vec::new();
+) -> impl Parser<I, Vec<O1>, E, C> {
+    separated_into(p, sep, ignore_dangling)
+}
+
+/// Like `separated`, but collects the outputs into any collection that can be extended. See
+/// `many_into`.
+///
+///```
+/// # use errgonomic::combinators::{one_of, is, separated_into};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (_, parsed): (State<&str>, String) =
+///     separated_into(one_of("abc").map(|c: Input<&str>| c.as_inner()), is(","), false)
+///         .process("a,b,c".into())
+///         .unwrap();
+/// assert_eq!(parsed, "abc");
+///```
+#[track_caller]
+pub fn separated_into<I: Underlying, O1, O2, T, E: CustomError, C, P1, P2>(
+    p: P1,
+    sep: P2,
+    ignore_dangling: bool,
+) -> impl Parser<I, T, E, C>
+where
+    T: Default + Extend<O1>,
+    P1: Parser<I, O1, E, C>,
+    P2: Parser<I, O2, E, C>,
+{
+    let location = caller();
+
+    move |state: State<I, E, C>| -> Result<I, T, E, C> {
+        let mut results = T::default();
 
         let (mut state, o) = match p.process(state.fork()) {
             Ok((new_state, o)) => (new_state, o),
@@ -44,22 +84,27 @@ pub fn separated<
                 return Ok((state, results));
             }
         };
-        results.push(o);
+        results = extend(results, o);
 
         while let Ok((new_state, _)) = sep.process(state.fork()) {
+            let before = state;
             state = new_state;
 
             if ignore_dangling {
                 if let Ok((new_state, o)) = p.process(state.fork()) {
                     state = new_state;
-                    results.push(o);
+                    results = extend(results, o);
                 } else {
                     break;
                 }
             } else {
                 let (new_state, o) = p.process(state.fork())?;
                 state = new_state;
-                results.push(o);
+                results = extend(results, o);
+            }
+
+            if !progressed(&before, &state) {
+                return Err(no_progress(before, location));
             }
         }
 
@@ -67,11 +112,240 @@ pub fn separated<
     }
 }
 
+/// What a `SeparatedList` does with a separator after the last element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Trailing {
+    /// A trailing separator is an error (`ElementAfter`), like `separated` with `ignore_dangling`
+    /// off.
+    #[default]
+    Forbidden,
+
+    /// A trailing separator is consumed if there is one, like `separated` with `ignore_dangling`
+    /// on.
+    Optional,
+
+    /// A (non-empty) list must end with a separator.
+    Required,
+}
+
+/// Creates a list of `p`, separated by `sep`. See `SeparatedList` for the options.
+///
+/// ```
+/// # use errgonomic::combinators::{separated_list, decimal, is, Trailing};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// let list = separated_list(decimal, is(",")).min(1).trailing(Trailing::Optional);
+///
+/// let (state, parsed): (State<&str>, Vec<Input<&str>>) = list.process("1,2,3,]".into()).unwrap();
+/// assert_eq!(parsed, vec!["1", "2", "3"]);
+/// assert_eq!(state.as_input().as_inner(), "]");
+///
+/// let state: State<&str> = separated_list(decimal, is(",")).process("1,]".into()).unwrap_err();
+/// assert_eq!(state.errors().to_string(), "expected element after `,`");
+/// ```
+#[track_caller]
+pub fn separated_list<I, O1, O2, E, C, P1, P2>(
+    p: P1,
+    sep: P2,
+) -> SeparatedList<I, O1, O2, E, P1, P2>
+where
+    I: Underlying,
+    E: CustomError,
+    P1: Parser<I, O1, E, C>,
+    P2: Parser<I, O2, E, C>,
+{
+    SeparatedList {
+        p,
+        sep,
+        min: 0,
+        max: usize::MAX,
+        trailing: Trailing::Forbidden,
+        location: caller(),
+        _marker: std::marker::PhantomData,
+    }
+}
+
+/// A list of elements separated by separators. See `separated_list`.
+///
+/// By default, any number of elements (including none) are parsed, and a trailing separator is an
+/// error. If an element fails after a separator, and a trailing separator isn't allowed, an
+/// `ElementAfter` error is returned over the separator.
+pub struct SeparatedList<I: Underlying, O1, O2, E: CustomError, P1, P2> {
+    /// The element parser.
+    p: P1,
+
+    /// The separator parser.
+    sep: P2,
+
+    /// The minimum number of elements.
+    min: usize,
+
+    /// The maximum number of elements. Parsing stops once it is reached.
+    max: usize,
+
+    /// What to do with a separator after the last element.
+    trailing: Trailing,
+
+    /// Where the list was created, for `NoProgress` errors.
+    location: Option<&'static Location<'static>>,
+
+    _marker: std::marker::PhantomData<(I, O1, O2, E)>,
+}
+
+impl<I, O1, O2, E, P1, P2> SeparatedList<I, O1, O2, E, P1, P2>
+where
+    I: Underlying,
+    E: CustomError,
+{
+    /// Sets the minimum number of elements. If there are less, the error that ended the list is
+    /// returned.
+    pub fn min(mut self, min: usize) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Sets the maximum number of elements.
+    pub fn max(mut self, max: usize) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Sets what to do with a separator after the last element.
+    pub fn trailing(mut self, trailing: Trailing) -> Self {
+        self.trailing = trailing;
+        self
+    }
+
+    /// Returns the separators as well as the elements, e.g. for lossless syntax trees.
+    ///
+    /// ```
+    /// # use errgonomic::combinators::{separated_list, alphabetic, is, Trailing};
+    /// # use errgonomic::parser::Parser;
+    /// # use errgonomic::parser::input::Input;
+    /// # use errgonomic::parser::state::State;
+    /// let (_, (elements, separators)): (State<&str>, (Vec<Input<&str>>, Vec<Input<&str>>)) =
+    ///     separated_list(alphabetic, is(";"))
+    ///         .trailing(Trailing::Required)
+    ///         .with_separators()
+    ///         .process("a;b;".into())
+    ///         .unwrap();
+    /// assert_eq!(elements, vec!["a", "b"]);
+    /// assert_eq!(separators.len(), 2);
+    /// ```
+    pub fn with_separators<C>(self) -> impl Parser<I, (Vec<O1>, Vec<O2>), E, C>
+    where
+        P1: Parser<I, O1, E, C>,
+        P2: Parser<I, O2, E, C>,
+    {
+        move |state| self.list(state)
+    }
+
+    /// INTERNAL: Parses the list, returning the elements and the separators.
+    fn list<C>(&self, state: State<I, E, C>) -> Result<I, (Vec<O1>, Vec<O2>), E, C>
+    where
+        P1: Parser<I, O1, E, C>,
+        P2: Parser<I, O2, E, C>,
+    {
+        assert!(
+            self.min <= self.max,
+            "The minimum number of elements can't be more than the maximum!"
+        );
+
+        let mut elements = vec![];
+        let mut separators = vec![];
+        if self.max == 0 {
+            return Ok((state, (elements, separators)));
+        }
+
+        let mut state = match self.p.process(state.fork()) {
+            Ok((new_state, o)) => {
+                elements.push(o);
+                new_state
+            }
+            Err(e) if self.min == 0 && !e.errors().is_committed() => {
+                return Ok((state, (elements, separators)))
+            }
+            Err(e) => return Err(e),
+        };
+
+        // The error that ended the list, for when there are too few elements.
+        let mut ended_by = None;
+
+        loop {
+            let (sep_state, s) = match self.sep.process(state.fork()) {
+                Ok(x) => x,
+                Err(e) if e.errors().is_committed() || self.trailing == Trailing::Required => {
+                    return Err(e)
+                }
+                Err(e) => {
+                    ended_by = Some(e);
+                    break;
+                }
+            };
+
+            // A full list can only have a trailing separator
+            if elements.len() >= self.max {
+                if self.trailing != Trailing::Forbidden {
+                    separators.push(s);
+                    state = sep_state;
+                }
+                break;
+            }
+
+            match self.p.process(sep_state.fork()) {
+                Ok((new_state, o)) => {
+                    if !progressed(&state, &new_state) {
+                        return Err(no_progress(state, self.location));
+                    }
+
+                    separators.push(s);
+                    elements.push(o);
+                    state = new_state;
+                }
+                Err(e) if e.errors().is_committed() => return Err(e),
+                Err(_) if self.trailing == Trailing::Forbidden => {
+                    let found = state.as_input().fork().subtract(sep_state.as_input());
+                    return Err(sep_state.with_error(Error::new(
+                        ErrorKind::expected(ExpectedError::ElementAfter(found.as_inner())),
+                        found,
+                    )));
+                }
+                Err(e) => {
+                    separators.push(s);
+                    state = sep_state;
+                    ended_by = Some(e);
+                    break;
+                }
+            }
+        }
+
+        if elements.len() < self.min {
+            return Err(ended_by.expect("A list that isn't full to have been ended by an error"));
+        }
+
+        Ok((state, (elements, separators)))
+    }
+}
+
+impl<I, O1, O2, E, C, P1, P2> Parser<I, Vec<O1>, E, C> for SeparatedList<I, O1, O2, E, P1, P2>
+where
+    I: Underlying,
+    E: CustomError,
+    P1: Parser<I, O1, E, C>,
+    P2: Parser<I, O2, E, C>,
+{
+    fn process(&self, state: State<I, E, C>) -> Result<I, Vec<O1>, E, C> {
+        let (state, (elements, _)) = self.list(state)?;
+        Ok((state, elements))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
-    use crate::combinators::is;
+    use crate::combinators::{any, is, maybe, one_of};
     use crate::parser::errors::{Error, ErrorKind, ExpectedError};
     use crate::parser::input::Input;
 
@@ -126,4 +400,130 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn can_parse_separated_into() {
+        let (_, parsed): (State<&str>, String) = separated_into(
+            one_of("abc").map(|c: Input<&str>| c.as_inner()),
+            is(","),
+            false,
+        )
+        .process("a,b,c".into())
+        .unwrap();
+        assert_eq!(parsed, "abc");
+    }
+
+    #[test]
+    fn separated_stops_without_progress() {
+        let state: State<&str> = separated(maybe(is("x")), maybe(is(",")), false)
+            .process("x,x;".into())
+            .unwrap_err();
+        assert_eq!(state.as_input(), &";");
+        assert!(matches!(state.errors().kind(), ErrorKind::NoProgress(_)));
+    }
+
+    #[test]
+    fn separated_list_handles_trailing() {
+        let list = |trailing| separated_list(is("a"), is(",")).trailing(trailing);
+
+        let state: State<&str> = list(Trailing::Forbidden)
+            .process("a,a,]".into())
+            .unwrap_err();
+        assert_eq!(state.as_input(), &"]");
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::ElementAfter(",")),
+                Input::new_with_span("a,a,]", 3..4)
+            )
+        );
+        assert_eq!(state.errors().to_string(), "expected element after `,`");
+
+        let (state, parsed): (State<&str>, Vec<Input<&str>>) =
+            list(Trailing::Forbidden).process("a,a]".into()).unwrap();
+        assert_eq!(parsed, ["a", "a"]);
+        assert_eq!(state.as_input(), &"]");
+
+        for input in ["a,a]", "a,a,]"] {
+            let (state, parsed): (State<&str>, Vec<Input<&str>>) =
+                list(Trailing::Optional).process(input.into()).unwrap();
+            assert_eq!(parsed, ["a", "a"]);
+            assert_eq!(state.as_input(), &"]");
+        }
+
+        let (state, parsed): (State<&str>, Vec<Input<&str>>) =
+            list(Trailing::Required).process("a,a,]".into()).unwrap();
+        assert_eq!(parsed, ["a", "a"]);
+        assert_eq!(state.as_input(), &"]");
+
+        let state: State<&str> = list(Trailing::Required).process("a,a]".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Is(",")),
+                Input::new_with_span("a,a]", 3..4)
+            )
+        );
+
+        // Empty lists don't need a separator
+        let (state, parsed): (State<&str>, Vec<Input<&str>>) =
+            list(Trailing::Required).process("]".into()).unwrap();
+        assert!(parsed.is_empty());
+        assert!(!state.is_err());
+    }
+
+    #[test]
+    fn separated_list_handles_counts() {
+        let state: State<&str> = separated_list(is("a"), is(","))
+            .min(1)
+            .process("b".into())
+            .unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Is("a")),
+                Input::new_with_span("b", 0..1)
+            )
+        );
+
+        let state: State<&str> = separated_list(is("a"), is(","))
+            .min(3)
+            .process("a,a;".into())
+            .unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Is(",")),
+                Input::new_with_span("a,a;", 3..4)
+            )
+        );
+
+        let (state, parsed): (State<&str>, Vec<Input<&str>>) = separated_list(is("a"), is(","))
+            .max(2)
+            .process("a,a,a".into())
+            .unwrap();
+        assert_eq!(parsed, ["a", "a"]);
+        assert_eq!(state.as_input(), &",a");
+
+        let (state, parsed): (State<&str>, Vec<Input<&str>>) = separated_list(is("a"), is(","))
+            .max(2)
+            .trailing(Trailing::Optional)
+            .process("a,a,a".into())
+            .unwrap();
+        assert_eq!(parsed, ["a", "a"]);
+        assert_eq!(state.as_input(), &"a");
+    }
+
+    #[test]
+    fn separated_list_returns_separators() {
+        let (state, (elements, separators)): (State<&str>, (Vec<Input<&str>>, Vec<_>)) =
+            separated_list(is("a"), any((is(","), is(";"))))
+                .trailing(Trailing::Optional)
+                .with_separators()
+                .process("a,a;a;".into())
+                .unwrap();
+        assert_eq!(elements, ["a", "a", "a"]);
+        assert_eq!(separators, [",", ";", ";"]);
+        assert!(state.as_input().is_empty());
+    }
 }
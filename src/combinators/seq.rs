@@ -0,0 +1,262 @@
+use crate::parser::{
+    errors::{CustomError, Result},
+    input::Underlying,
+    state::State,
+    Parser,
+};
+use eval_macro::eval;
+
+/// Parses all of the given parsers in sequence, and returns all of their outputs as a flat tuple.
+/// If any of the parsers fail, the error is returned. Unlike chaining `then`, the outputs aren't
+/// nested, i.e. `seq((a, b, c))` outputs `(a, b, c)` instead of `((a, b), c)`.
+///
+/// NOTE: Up to 20 parsers are supported. Use `preceded`, `terminated`, `separated_pair` or
+/// `ignore` to drop outputs you don't need.
+///
+///```
+/// # use errgonomic::combinators::{seq, decimal, is};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// let (state, (x, _, y)): (State<&str>, (Input<&str>, Input<&str>, Input<&str>)) =
+///     seq((decimal, is(","), decimal)).process("1,2)".into()).unwrap();
+/// assert_eq!(x, "1");
+/// assert_eq!(y, "2");
+/// assert_eq!(state.as_input().as_inner(), ")");
+///```
+#[inline]
+#[allow(private_bounds)]
+pub fn seq<I: Underlying, O, E: CustomError, C, S: Sequence<I, O, E, C>>(
+    ps: S,
+) -> impl Parser<I, O, E, C> {
+    move |state| ps.seq(state)
+}
+
+/// Parses `first` and then `second`, but only returns the output of `second`.
+///
+///```
+/// # use errgonomic::combinators::{preceded, decimal, is};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::errors::DummyError;
+/// let parsed = preceded(is::<_, DummyError, ()>("-"), decimal).parse("-123").unwrap();
+/// assert_eq!(parsed, "123");
+///```
+pub fn preceded<I: Underlying, O1, O2, E: CustomError, C, P1, P2>(
+    first: P1,
+    second: P2,
+) -> impl Parser<I, O2, E, C>
+where
+    P1: Parser<I, O1, E, C>,
+    P2: Parser<I, O2, E, C>,
+{
+    first.then(second).map(|(_, o)| o)
+}
+
+/// Parses `first` and then `second`, but only returns the output of `first`.
+///
+///```
+/// # use errgonomic::combinators::{terminated, decimal, is};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::errors::DummyError;
+/// let parsed = terminated(decimal::<_, DummyError, ()>, is(";")).parse("123;").unwrap();
+/// assert_eq!(parsed, "123");
+///```
+pub fn terminated<I: Underlying, O1, O2, E: CustomError, C, P1, P2>(
+    first: P1,
+    second: P2,
+) -> impl Parser<I, O1, E, C>
+where
+    P1: Parser<I, O1, E, C>,
+    P2: Parser<I, O2, E, C>,
+{
+    first.then(second).map(|(o, _)| o)
+}
+
+/// Parses `first`, `sep` and then `second`, and returns the outputs of `first` and `second`.
+///
+///```
+/// # use errgonomic::combinators::{separated_pair, alphabetic, decimal, is};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::errors::DummyError;
+/// let (key, value) = separated_pair(alphabetic::<_, DummyError, ()>, is("="), decimal).parse("x=1").unwrap();
+/// assert_eq!(key, "x");
+/// assert_eq!(value, "1");
+///```
+pub fn separated_pair<I: Underlying, O1, O2, O3, E: CustomError, C, P1, P2, P3>(
+    first: P1,
+    sep: P2,
+    second: P3,
+) -> impl Parser<I, (O1, O3), E, C>
+where
+    P1: Parser<I, O1, E, C>,
+    P2: Parser<I, O2, E, C>,
+    P3: Parser<I, O3, E, C>,
+{
+    seq((first, sep, second)).map(|(o1, _, o3)| (o1, o3))
+}
+
+/// Parses `p`, but throws away its output. Useful for positions in a `seq` whose output isn't
+/// needed.
+///
+///```
+/// # use errgonomic::combinators::{ignore, seq, decimal, is};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::errors::DummyError;
+/// let (x, (), y) = seq((decimal::<_, DummyError, ()>, ignore(is("+")), decimal)).parse("1+2").unwrap();
+/// assert_eq!(x, "1");
+/// assert_eq!(y, "2");
+///```
+pub fn ignore<I: Underlying, O, E: CustomError, C, P: Parser<I, O, E, C>>(
+    p: P,
+) -> impl Parser<I, (), E, C> {
+    p.map(|_| ())
+}
+
+/* TRAIT IMPLEMENTATIONS NEEDED FOR SEQ */
+/* These are annoying and long, you can ignore*/
+
+trait Sequence<I: Underlying, O, E: CustomError, C> {
+    fn seq(&self, state: State<I, E, C>) -> Result<I, O, E, C>;
+}
+
+// Magic macro magic that makes the impl. of `Sequence` for (nearly!) all tuples of parsers.
+// See: https://crates.io/crates/eval-macro
+eval! {
+    const UP_TO: usize = 20; // NOTE: The maximum size of the parser-tuples we want to implement.
+
+    for n in 1..=UP_TO {
+        let parser_generics = (1..=n)
+            .map(|i| format!("P{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let output_generics = (1..=n)
+            .map(|i| format!("O{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        // NOTE: Leading comma so that this also works for the single-tuple
+        let parser_tuple = format!("({parser_generics},)");
+        let output_tuple = format!("({output_generics},)");
+        let parser_defs = (1..=n)
+            .map(|i| format!("P{i}: Parser<I, O{i}, E, C>"))
+            .collect::<Vec<_>>()
+            .join(",\n");
+        let processing = (0..n)
+            .map(|i| format!("let (state, o{i}) = self.{i}.process(state)?;"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let outputs = (0..n)
+            .map(|i| format!("o{i},"))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        output! {
+            impl<I, E, C, {{output_generics}}, {{parser_generics}}> Sequence<I, {{output_tuple}}, E, C> for {{parser_tuple}}
+            where
+                I: Underlying,
+                E: CustomError,
+                {{parser_defs}}
+            {
+                #[inline]
+                fn seq(&self, state: State<I, E, C>) -> Result<I, {{output_tuple}}, E, C> {
+                    {{processing}}
+
+                    Ok((state, ({{outputs}})))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        combinators::{alphabetic, decimal, is},
+        parser::{
+            errors::{Error, ErrorKind, ExpectedError},
+            input::Input,
+        },
+    };
+
+    #[test]
+    fn can_parse_seq() {
+        let (state, (a, b, c)): (State<&str>, _) = seq((alphabetic, decimal, is("!")))
+            .process("abc123!?".into())
+            .unwrap();
+        assert_eq!(a, "abc");
+        assert_eq!(b, "123");
+        assert_eq!(c, "!");
+        assert_eq!(state.as_input(), &"?");
+        assert!(!state.is_err());
+
+        let (_, (a,)): (State<&str>, (Input<&str>,)) = seq((is("x"),)).process("x".into()).unwrap();
+        assert_eq!(a, "x");
+
+        let state: State<&str> = seq((alphabetic, decimal, is("!")))
+            .process("abc123?".into())
+            .unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Is("!")),
+                Input::new_with_span("abc123?", 6..7)
+            )
+        );
+    }
+
+    #[test]
+    fn can_parse_seq_of_20() {
+        let x = || is("x");
+        let (state, parsed): (State<&str>, _) = seq((
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            x(),
+            is("y"),
+        ))
+        .process("xxxxxxxxxxxxxxxxxxxy".into())
+        .unwrap();
+        assert_eq!(parsed.19, "y");
+        assert!(state.as_input().is_empty());
+    }
+
+    #[test]
+    fn can_drop_outputs() {
+        let (_, parsed): (State<&str>, Input<&str>) =
+            preceded(is("-"), decimal).process("-1".into()).unwrap();
+        assert_eq!(parsed, "1");
+
+        let (state, parsed): (State<&str>, Input<&str>) =
+            terminated(decimal, is(";")).process("1;2".into()).unwrap();
+        assert_eq!(parsed, "1");
+        assert_eq!(state.as_input(), &"2");
+
+        let (_, parsed): (State<&str>, (Input<&str>, Input<&str>)) =
+            separated_pair(alphabetic, is(":"), decimal)
+                .process("a:1".into())
+                .unwrap();
+        assert_eq!(parsed.0, "a");
+        assert_eq!(parsed.1, "1");
+
+        let (_, parsed): (State<&str>, _) = seq((decimal, ignore(is("+")), decimal))
+            .process("1+2".into())
+            .unwrap();
+        assert_eq!(parsed.0, "1");
+        assert_eq!(parsed.2, "2");
+    }
+}
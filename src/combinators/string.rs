@@ -0,0 +1,464 @@
+use crate::parser::{
+    errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
+    input::Input,
+    state::State,
+    Parser,
+};
+use std::borrow::Cow;
+
+/// Parses a quoted string literal, with escapes, e.g. `"a\tb\u{1F600}"`, into its contents. The
+/// contents are borrowed from the input when there are no escapes, and only allocated otherwise.
+///
+/// By default, the literal is quoted with `"`, and supports the escapes `\n`, `\r`, `\t`, `\0`,
+/// `\\`, `\"` and `\'`, along with `\u{…}` (1-6 hex digits), `\uXXXX` (with surrogate pairs) and
+/// `\xNN`, as well as raw strings (`r"…"` or `r#"…"#`, without escapes). See `StringLiteral` for
+/// changing any of these.
+///
+/// Every invalid escape gets its own `Escape` error, spanning exactly the escape (so they can all
+/// be reported at once), and an unterminated literal gets a `Missing` error for its closing quote.
+///
+/// NOTE: Only `&str` inputs are supported, as the contents are borrowed as a `str`.
+///
+/// ```
+/// # use errgonomic::combinators::string_literal;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use std::borrow::Cow;
+/// let (state, parsed): (State<&str>, Cow<str>) =
+///     string_literal().process(r#""tab\there" rest"#.into()).unwrap();
+/// assert_eq!(parsed, "tab\there");
+/// assert_eq!(state.as_input().as_inner(), " rest");
+///
+/// // No escapes, so no allocation
+/// let (_, parsed): (State<&str>, Cow<str>) =
+///     string_literal().process(r##"r#"C:\path"#"##.into()).unwrap();
+/// assert!(matches!(parsed, Cow::Borrowed(r"C:\path")));
+///
+/// let state: State<&str> = string_literal().process(r#""\q \u{110000}""#.into()).unwrap_err();
+/// assert_eq!(
+///     state.errors().to_string(),
+///     "expected a valid escape sequence\nexpected a valid escape sequence"
+/// );
+/// ```
+pub fn string_literal<E: CustomError, C>() -> StringLiteral<E, C> {
+    StringLiteral {
+        quotes: vec!["\""],
+        escapes: vec![
+            ('n', '\n'),
+            ('r', '\r'),
+            ('t', '\t'),
+            ('0', '\0'),
+            ('\\', '\\'),
+            ('"', '"'),
+            ('\'', '\''),
+        ],
+        unicode: true,
+        utf16: true,
+        hex: true,
+        raw: true,
+        _marker: std::marker::PhantomData,
+    }
+}
+
+/// A string literal parser, with its quotes and escape table. See `string_literal`.
+///
+/// ```
+/// # use errgonomic::combinators::string_literal;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::errors::DummyError;
+/// // Like SQL strings: either quote, no raw strings, and only `\n` and `\'`
+/// let mut sql = string_literal::<DummyError, ()>()
+///     .quotes(["'", "\""])
+///     .without_escapes()
+///     .escape('n', '\n')
+///     .escape('\'', '\'')
+///     .raw(false);
+/// assert_eq!(sql.parse(r"'it\'s'").unwrap(), "it's");
+/// assert!(sql.parse(r"'\t'").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct StringLiteral<E: CustomError, C = ()> {
+    /// The quotes a literal can be in, where the closing quote is the same as the opening one.
+    quotes: Vec<&'static str>,
+
+    /// The simple escapes, i.e. `\` followed by the first `char`, for the second `char`.
+    escapes: Vec<(char, char)>,
+
+    /// Whether `\u{…}` escapes are allowed.
+    unicode: bool,
+
+    /// Whether `\uXXXX` escapes (with surrogate pairs) are allowed.
+    utf16: bool,
+
+    /// Whether `\xNN` escapes are allowed.
+    hex: bool,
+
+    /// Whether raw strings are allowed.
+    raw: bool,
+
+    _marker: std::marker::PhantomData<(E, C)>,
+}
+
+impl<E: CustomError, C> StringLiteral<E, C> {
+    /// Sets the quotes a literal can be in (where it's closed with the same quote it's opened with).
+    ///
+    /// NOTE: Panics if there are no quotes, or any of them are empty.
+    pub fn quotes<L: IntoIterator<Item = &'static str>>(mut self, quotes: L) -> Self {
+        self.quotes = quotes.into_iter().collect();
+        assert!(
+            !self.quotes.is_empty() && self.quotes.iter().all(|q| !q.is_empty()),
+            "There should be at least 1 quote, and no empty quotes!"
+        );
+        self
+    }
+
+    /// Adds a simple escape, i.e. `\` followed by `escape` means `value`. If `escape` was already
+    /// in the table, its value is replaced.
+    pub fn escape(mut self, escape: char, value: char) -> Self {
+        match self.escapes.iter_mut().find(|(e, _)| *e == escape) {
+            Some(entry) => entry.1 = value,
+            None => self.escapes.push((escape, value)),
+        }
+        self
+    }
+
+    /// Removes all the escapes (including `\u` and `\x`), so that they can be added back one by
+    /// one.
+    pub fn without_escapes(mut self) -> Self {
+        self.escapes.clear();
+        self.unicode = false;
+        self.utf16 = false;
+        self.hex = false;
+        self
+    }
+
+    /// Sets whether `\u{…}` escapes are allowed.
+    pub fn unicode(mut self, unicode: bool) -> Self {
+        self.unicode = unicode;
+        self
+    }
+
+    /// Sets whether `\uXXXX` escapes are allowed. Surrogates must come in pairs, e.g. `\uD83D\uDE00`.
+    pub fn utf16(mut self, utf16: bool) -> Self {
+        self.utf16 = utf16;
+        self
+    }
+
+    /// Sets whether `\xNN` escapes are allowed.
+    pub fn hex(mut self, hex: bool) -> Self {
+        self.hex = hex;
+        self
+    }
+
+    /// Sets whether raw strings are allowed, i.e. `r` followed by any number of `#`, then a quote,
+    /// and closed by the quote followed by the same number of `#`.
+    pub fn raw(mut self, raw: bool) -> Self {
+        self.raw = raw;
+        self
+    }
+
+    /// INTERNAL: Gets the quote at the start of `s`, if any.
+    fn quote_at(&self, s: &str) -> Option<&'static str> {
+        self.quotes.iter().copied().find(|q| s.starts_with(q))
+    }
+
+    /// INTERNAL: Parses an escape at the start of `s` (just after the `\`). Returns its value (if
+    /// it's valid) and its length.
+    fn escape_at(&self, s: &str) -> (Option<char>, usize) {
+        let Some(c) = s.chars().next() else {
+            return (None, 0);
+        };
+
+        if let Some(&(_, value)) = self.escapes.iter().find(|(e, _)| *e == c) {
+            return (Some(value), c.len_utf8());
+        }
+
+        match c {
+            'u' if self.unicode && s[1..].starts_with('{') => {
+                // NOTE: All the digits are part of the (invalid) escape, even past the 6th.
+                let digits = hex_digits_len(&s[2..], usize::MAX);
+                let closed = s[2 + digits..].starts_with('}');
+                let value = match closed && (1..=6).contains(&digits) {
+                    true => u32::from_str_radix(&s[2..2 + digits], 16)
+                        .ok()
+                        .and_then(char::from_u32),
+                    false => None,
+                };
+                (value, 2 + digits + usize::from(closed))
+            }
+            'u' if self.utf16 => {
+                let Some(high) = hex_code(&s[1..], 4) else {
+                    return (None, 1 + hex_digits_len(&s[1..], 4));
+                };
+
+                match high {
+                    0xD800..=0xDBFF => {
+                        match s[5..].strip_prefix("\\u").and_then(|s| hex_code(s, 4)) {
+                            Some(low @ 0xDC00..=0xDFFF) => (
+                                char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)),
+                                11,
+                            ),
+                            _ => (None, 5),
+                        }
+                    }
+                    high => (char::from_u32(high), 5),
+                }
+            }
+            'x' if self.hex => match hex_code(&s[1..], 2) {
+                Some(code) => (char::from_u32(code), 3),
+                None => (None, 1 + hex_digits_len(&s[1..], 2)),
+            },
+            c => (None, c.len_utf8()),
+        }
+    }
+
+    /// INTERNAL: Parses a raw string at the start of `s`, if they're allowed. Returns its quote,
+    /// and its contents and length, if it's closed.
+    fn raw_at<'a>(&self, s: &'a str) -> Option<(&'static str, Option<(&'a str, usize)>)> {
+        let s = s.strip_prefix('r').filter(|_| self.raw)?;
+        let hashes = &s[..s.len() - s.trim_start_matches('#').len()];
+        let quote = self.quote_at(&s[hashes.len()..])?;
+        let open = hashes.len() + quote.len();
+
+        let closed = s[open..]
+            .match_indices(quote)
+            .find(|&(i, _)| s[open + i + quote.len()..].starts_with(hashes))
+            .map(|(i, _)| {
+                (
+                    &s[open..open + i],
+                    1 + open + i + quote.len() + hashes.len(),
+                )
+            });
+        Some((quote, closed))
+    }
+}
+
+impl<'a, E: CustomError, C> Parser<&'a str, Cow<'a, str>, E, C> for StringLiteral<E, C> {
+    fn process(&self, state: State<&'a str, E, C>) -> Result<&'a str, Cow<'a, str>, E, C> {
+        let input = state.as_input().fork();
+        let s = input.as_inner();
+
+        if let Some((quote, closed)) = self.raw_at(s) {
+            return match closed {
+                Some((contents, len)) => {
+                    Ok((state.with_input(input.skip(len)), Cow::Borrowed(contents)))
+                }
+                None => Err(state.with_error(unterminated(&input, quote))),
+            };
+        }
+
+        let Some(quote) = self.quote_at(s) else {
+            let mut errors = self.quotes.iter().map(|&q| {
+                Error::new(
+                    ErrorKind::expected(ExpectedError::Is(q)),
+                    input.take(q.len()),
+                )
+            });
+            let error = match self.quotes.len() {
+                1 => errors.next().expect("a quote"),
+                _ => Error::new(ErrorKind::all(errors.collect()), input.take(1)),
+            };
+            return Err(state.with_error(error));
+        };
+
+        let start = quote.len();
+        let mut owned: Option<String> = None;
+        let mut errors = vec![];
+        let mut i = start;
+
+        loop {
+            let rest = &s[i..];
+            if rest.starts_with(quote) {
+                break;
+            }
+
+            match rest.chars().next() {
+                None => return Err(state.with_error(unterminated(&input, quote))),
+                Some('\\') => {
+                    let (value, len) = self.escape_at(&rest[1..]);
+                    if len == 0 {
+                        return Err(state.with_error(unterminated(&input, quote)));
+                    }
+
+                    match value {
+                        Some(c) => owned.get_or_insert_with(|| s[start..i].to_string()).push(c),
+                        None => errors.push(Error::new(
+                            ErrorKind::expected(ExpectedError::Escape),
+                            input.skip(i).take(1 + len),
+                        )),
+                    }
+                    i += 1 + len;
+                }
+                Some(c) => {
+                    if let Some(owned) = &mut owned {
+                        owned.push(c);
+                    }
+                    i += c.len_utf8();
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors
+                .into_iter()
+                .fold(state, |state, error| state.with_error(error)));
+        }
+
+        let contents = match owned {
+            Some(owned) => Cow::Owned(owned),
+            None => Cow::Borrowed(&s[start..i]),
+        };
+        Ok((state.with_input(input.skip(i + quote.len())), contents))
+    }
+}
+
+/// INTERNAL: The error for a literal without its closing quote, spanning the whole literal.
+fn unterminated<'a, E: CustomError>(
+    input: &Input<&'a str>,
+    quote: &'static str,
+) -> Error<&'a str, E> {
+    let missing = Error::new(
+        ErrorKind::expected(ExpectedError::Is(quote)),
+        input.skip_all(),
+    );
+    Error::new(ErrorKind::missing(missing), input.fork())
+}
+
+/// INTERNAL: Gets the number of hex digits (up to `max`) at the start of `s`.
+fn hex_digits_len(s: &str, max: usize) -> usize {
+    s.bytes()
+        .take(max)
+        .take_while(u8::is_ascii_hexdigit)
+        .count()
+}
+
+/// INTERNAL: Gets the value of exactly `len` hex digits at the start of `s`.
+fn hex_code(s: &str, len: usize) -> Option<u32> {
+    match hex_digits_len(s, len) == len {
+        true => u32::from_str_radix(&s[..len], 16).ok(),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::errors::DummyError;
+
+    fn parse(input: &str) -> core::result::Result<Cow<'_, str>, Error<&str, DummyError>> {
+        string_literal::<DummyError, ()>().parse(input)
+    }
+
+    #[test]
+    fn can_parse_strings() {
+        assert!(matches!(parse(r#""abc""#), Ok(Cow::Borrowed("abc"))));
+        assert!(matches!(parse(r#""""#), Ok(Cow::Borrowed(""))));
+        assert!(matches!(parse("\"é\n☃\""), Ok(Cow::Borrowed("é\n☃"))));
+
+        let (state, parsed): (State<&str>, Cow<str>) =
+            string_literal().process(r#""a\"b" "c""#.into()).unwrap();
+        assert_eq!(parsed, "a\"b");
+        assert_eq!(state.as_input(), &r#" "c""#);
+    }
+
+    #[test]
+    fn can_parse_escapes() {
+        for (input, expected) in [
+            (r#""\n\r\t\0\\\"\'""#, "\n\r\t\0\\\"'"),
+            (r#""é\té""#, "é\té"),
+            (r#""\u{41}\u{1F600}\u{00000a}""#, "A😀\n"),
+            (r#""Aé😀""#, "Aé😀"),
+            (r#""\x41\x7f""#, "A\x7f"),
+        ] {
+            assert_eq!(parse(input).as_deref(), Ok(expected), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn can_parse_raw_strings() {
+        assert!(matches!(parse(r#"r"a\n""#), Ok(Cow::Borrowed(r"a\n"))));
+        assert!(matches!(
+            parse(r###"r##"a"#b"##"###),
+            Ok(Cow::Borrowed(r##"a"#b"##))
+        ));
+
+        let (state, parsed): (State<&str>, Cow<str>) =
+            string_literal().process(r##"r#"a"#"#"##.into()).unwrap();
+        assert_eq!(parsed, "a");
+        assert_eq!(state.as_input(), &"\"#");
+
+        // Not a raw string without a quote, or if they're disabled
+        assert!(parse("r#a").is_err());
+        assert!(string_literal::<DummyError, ()>()
+            .raw(false)
+            .parse(r#"r"a""#)
+            .is_err());
+    }
+
+    #[test]
+    fn each_invalid_escape_is_reported() {
+        let input = r#""\q\u{110000}\u{}\uD800x\uDC00\u12\xZ1\u{1234567}" rest"#;
+        let state: State<&str> = string_literal().process(input.into()).unwrap_err();
+        assert_eq!(state.as_input(), &input);
+
+        let spans = [1..3, 3..13, 13..17, 17..23, 24..30, 30..34, 34..36, 38..49];
+        let expected = spans
+            .into_iter()
+            .fold(Error::empty(Input::new(input)), |mut e, span| {
+                e.push(Error::new(
+                    ErrorKind::expected(ExpectedError::Escape),
+                    Input::new_with_span(input, span),
+                ));
+                e
+            });
+        assert_eq!(state.errors(), &expected);
+    }
+
+    #[test]
+    fn strings_report_errors() {
+        let state: State<&str> = string_literal().process("'a'".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Is("\"")),
+                Input::new_with_span("'a'", 0..1)
+            )
+        );
+
+        for (input, quote) in [(r#""abc"#, "\""), (r#""abc\"#, "\""), (r##"r#"a""##, "\"")] {
+            let state: State<&str> = string_literal().process(input.into()).unwrap_err();
+            let missing = Error::new(
+                ErrorKind::expected(ExpectedError::Is(quote)),
+                Input::new_with_span(input, input.len()..input.len()),
+            );
+            assert_eq!(
+                state.errors(),
+                &Error::new(ErrorKind::missing(missing), Input::new(input)),
+                "{:?}",
+                input
+            );
+            assert_eq!(state.errors().to_string(), "missing `\"`");
+        }
+    }
+
+    #[test]
+    fn can_configure_quotes_and_escapes() {
+        let mut parser = string_literal::<DummyError, ()>()
+            .quotes(["'", "\"\"\""])
+            .escape('e', '\x1b')
+            .escape('n', 'N')
+            .unicode(false)
+            .hex(false);
+        assert_eq!(parser.parse(r#"'a"\e\n'"#).as_deref(), Ok("a\"\x1bN"));
+        assert_eq!(parser.parse(r#""""a"b""""#).as_deref(), Ok("a\"b"));
+        assert_eq!(parser.parse(r"'A'").as_deref(), Ok("A"));
+        assert!(parser.parse(r"'\u{41}'").is_err());
+        assert!(parser.parse(r"'\x41'").is_err());
+
+        let state: State<&str> = parser.process("x".into()).unwrap_err();
+        assert_eq!(
+            state.errors().to_string(),
+            "expected `'`, or expected `\"\"\"`"
+        );
+    }
+}
@@ -1,6 +1,7 @@
+use super::glyph::{glyphs, glyphs_len};
 use crate::parser::{
     errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
-    input::{Input, Underlying},
+    input::{decode_glyph, Input, Underlying},
     state::State,
     Parser,
 };
@@ -20,8 +21,8 @@ use crate::parser::{
 /// assert_eq!(parsed, "hello");
 /// assert_eq!(state.as_input().as_inner(), "hellohelloworld!");
 /// ```
-pub fn take<I: Underlying, E: CustomError>(n: usize) -> impl Parser<I, Input<I>, E> {
-    move |mut state: State<I, E>| {
+pub fn take<I: Underlying, E: CustomError, C>(n: usize) -> impl Parser<I, Input<I>, E, C> {
+    move |mut state: State<I, E, C>| {
         #[cfg(not(feature = "unicode"))]
         {
             let mut taken_len = 0;
@@ -68,9 +69,121 @@ pub fn take<I: Underlying, E: CustomError>(n: usize) -> impl Parser<I, Input<I>,
     }
 }
 
+/// Takes characters from the input as long as they match `f`, and returns them. If none match,
+/// returns an empty input.
+///
+/// NOTE: When `unicode` is enabled, `f` is called with whole unicode characters. Otherwise, it is
+/// called with each byte as a `char`.
+///
+/// ```
+/// # use errgonomic::combinators::take_while;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = take_while(|c| c != ';').process("a = 1; b".into()).unwrap();
+/// assert_eq!(parsed, "a = 1");
+/// assert_eq!(state.as_input().as_inner(), "; b");
+/// ```
+pub fn take_while<I: Underlying, E: CustomError, C, F: Fn(char) -> bool>(
+    f: F,
+) -> impl Parser<I, Input<I>, E, C> {
+    move |state: State<I, E, C>| -> Result<I, Input<I>, E, C> {
+        let input = state.as_input().fork();
+        let len = glyphs_len(input.as_bytes(), &f);
+        Ok((state.with_input(input.skip(len)), input.take(len)))
+    }
+}
+
+/// Takes characters from the input as long as they match `f`, and returns them. Unlike
+/// `take_while`, at least one character must match, or an error is returned.
+///
+/// ```
+/// # use errgonomic::combinators::take_while1;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = take_while1(|c| c.is_ascii_digit()).process("42abc".into()).unwrap();
+/// assert_eq!(parsed, "42");
+/// assert_eq!(state.as_input().as_inner(), "abc");
+/// ```
+pub fn take_while1<I: Underlying, E: CustomError, C, F: Fn(char) -> bool>(
+    f: F,
+) -> impl Parser<I, Input<I>, E, C> {
+    move |state: State<I, E, C>| glyphs(state, &f, ExpectedError::Satisfy)
+}
+
+/// Takes at least `m` and at most `n` characters from the input as long as they match `f`, and
+/// returns them. If fewer than `m` match, returns an error over the first character that doesn't.
+///
+/// ```
+/// # use errgonomic::combinators::take_while_m_n;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = take_while_m_n(2, 4, |c| c.is_ascii_hexdigit()).process("c0ffee".into()).unwrap();
+/// assert_eq!(parsed, "c0ff");
+/// assert_eq!(state.as_input().as_inner(), "ee");
+/// ```
+pub fn take_while_m_n<I: Underlying, E: CustomError, C, F: Fn(char) -> bool>(
+    m: usize,
+    n: usize,
+    f: F,
+) -> impl Parser<I, Input<I>, E, C> {
+    assert!(m <= n, "`m` should be less than or equal to `n`!");
+
+    move |state: State<I, E, C>| -> Result<I, Input<I>, E, C> {
+        let input = state.as_input().fork();
+        let bytes = input.as_bytes();
+        let mut len = 0;
+        let mut count = 0;
+
+        while count < n {
+            match decode_glyph(&bytes[len..]) {
+                Some((c, glyph_len)) if f(c) => len += glyph_len,
+                _ => break,
+            }
+            count += 1;
+        }
+
+        if count < m {
+            let glyph_len = decode_glyph(&bytes[len..]).map_or(1, |(_, glyph_len)| glyph_len);
+            return Err(state.with_error(Error::new(
+                ErrorKind::expected(ExpectedError::Satisfy),
+                input.skip(len).take(glyph_len),
+            )));
+        }
+
+        Ok((state.with_input(input.skip(len)), input.take(len)))
+    }
+}
+
+/// Takes characters from the input until one matches `f`, and returns them. The matching
+/// character is *not* consumed. If none match, returns the rest of the input.
+///
+/// ```
+/// # use errgonomic::combinators::take_till;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = take_till(|c| c == '\n').process("line 1\nline 2".into()).unwrap();
+/// assert_eq!(parsed, "line 1");
+/// assert_eq!(state.as_input().as_inner(), "\nline 2");
+/// ```
+pub fn take_till<I: Underlying, E: CustomError, C, F: Fn(char) -> bool>(
+    f: F,
+) -> impl Parser<I, Input<I>, E, C> {
+    take_while(move |c| !f(c))
+}
+
 /// Takes elements from the input until a parser `until` matches. The output of `until` will be
-/// included in the output. If we encounter an end-of-input before `until` matches, an error will
-/// be returned.
+/// included in the output.
+///
+/// `until` is tried at the start of the input, after every character, and finally at the end of
+/// the input (so `take_until(eoi)` takes the rest of the input). If it never matches, the error
+/// from trying it at the end of the input is returned.
+///
+/// NOTE: This tries `until` at every offset, so it can be slow on large inputs. If `until` is a
+/// literal, use `take_until_literal` instead.
 ///
 /// ```
 /// # use errgonomic::combinators::{take_until, is};
@@ -82,34 +195,74 @@ pub fn take<I: Underlying, E: CustomError>(n: usize) -> impl Parser<I, Input<I>,
 /// assert_eq!(until, "world");
 /// assert_eq!(state.as_input().as_inner(), "!");
 /// ```
-pub fn take_until<I: Underlying, O2, E: CustomError, P: Parser<I, O2, E>>(
+pub fn take_until<I: Underlying, O2, E: CustomError, C, P: Parser<I, O2, E, C>>(
     until: P,
-) -> impl Parser<I, (Input<I>, O2), E> {
-    move |mut state: State<I, E>| -> Result<I, (Input<I>, O2), E> {
-        let mut taken_len = 0;
+) -> impl Parser<I, (Input<I>, O2), E, C> {
+    move |state: State<I, E, C>| -> Result<I, (Input<I>, O2), E, C> {
         let original_input = state.as_input().fork();
+        let bytes = original_input.as_bytes();
+        let mut taken_len = 0;
 
         loop {
-            match until.process(state.fork()) {
+            let attempt = state.fork().with_input(original_input.skip(taken_len));
+            match until.process(attempt) {
                 Ok((new_state, o)) => {
                     return Ok((new_state, (original_input.take(taken_len), o)));
                 }
+                Err(err_state) if taken_len == bytes.len() => return Err(err_state),
                 Err(_) => {
-                    taken_len += 1;
-                    state = state.with_input(original_input.skip(taken_len));
+                    // NOTE: Invalid UTF-8 is skipped a byte at a time.
+                    taken_len += decode_glyph(&bytes[taken_len..]).map_or(1, |(_, len)| len);
                 }
             }
+        }
+    }
+}
+
+/// Takes elements from the input until the literal `until` is found. The matched literal will be
+/// included in the output. Like `take_until(is(until))`, but much faster, as it uses a
+/// substring search instead of trying to match at every offset.
+///
+/// If `until` is never found, an `Is` error is returned at the end of the input.
+///
+/// ```
+/// # use errgonomic::combinators::take_until_literal;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, (comment, close)): (State<&str>, (Input<&str>, Input<&str>)) =
+///     take_until_literal("*/").process(" a comment */ x".into()).unwrap();
+/// assert_eq!(comment, " a comment ");
+/// assert_eq!(close, "*/");
+/// assert_eq!(state.as_input().as_inner(), " x");
+/// ```
+pub fn take_until_literal<I: Underlying, E: CustomError, C>(
+    until: I,
+) -> impl Parser<I, (Input<I>, Input<I>), E, C> {
+    let finder = memchr::memmem::Finder::new(
+        until
+            .byte_span(0, until.len())
+            .expect("the literal to cover itself"),
+    )
+    .into_owned();
 
-            // HACK: Gets around the error where if we are `take`-ing until an `eoi` matches, we
-            // will always error before the `eoi` matches as we will check for a `None` first.
-            // TODO: Make this faster?
-            let future = state.fork().with_input(state.as_input().take(1));
-            if state.as_input().peek().is_none() && until.process(future).is_err() {
-                println!("input is none!");
-                return Err(state.with_error(Error::new(
-                    ErrorKind::expected(ExpectedError::Anything),
-                    original_input.skip(taken_len),
-                )));
+    move |state: State<I, E, C>| -> Result<I, (Input<I>, Input<I>), E, C> {
+        let input = state.as_input().fork();
+
+        match finder.find(input.as_bytes()) {
+            Some(taken_len) => {
+                let until_len = finder.needle().len();
+                Ok((
+                    state.with_input(input.skip(taken_len + until_len)),
+                    (input.take(taken_len), input.skip(taken_len).take(until_len)),
+                ))
+            }
+            None => {
+                let end = input.skip(input.as_bytes().len());
+                Err(state.with_error(Error::new(
+                    ErrorKind::expected(ExpectedError::Is(until.fork())),
+                    end,
+                )))
             }
         }
     }
@@ -119,7 +272,7 @@ pub fn take_until<I: Underlying, O2, E: CustomError, P: Parser<I, O2, E>>(
 mod tests {
     use super::*;
     use crate::{
-        combinators::is,
+        combinators::{eoi, is},
         parser::errors::{ErrorKind, ExpectedError},
     };
 
@@ -162,6 +315,162 @@ mod tests {
         assert!(!state.is_err());
     }
 
+    #[test]
+    fn take_until_tries_end_of_input() {
+        let (state, (parsed, _)): (State<&str>, (Input<&str>, ())) =
+            take_until(eoi).process("hello".into()).unwrap();
+        assert_eq!(parsed, "hello");
+        assert_eq!(state.as_input().as_inner(), "");
+
+        let (_, (parsed, _)): (State<&str>, (Input<&str>, ())) =
+            take_until(eoi).process("".into()).unwrap();
+        assert_eq!(parsed, "");
+
+        // The error is `until`'s, at the end of the input
+        let state: State<&str> = take_until(is("*/")).process("abc".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Is("*/")),
+                Input::new_with_span("abc", 3..3)
+            )
+        );
+    }
+
+    #[test]
+    fn can_take_until_literal() {
+        let (state, (parsed, until)): (State<&str>, (Input<&str>, Input<&str>)) =
+            take_until_literal("world")
+                .process("hellohellohelloworld!".into())
+                .unwrap();
+        assert_eq!(parsed, "hellohellohello");
+        assert_eq!(until, "world");
+        assert_eq!(state.as_input().as_inner(), "!");
+        assert!(!state.is_err());
+
+        let state: State<&str> = take_until_literal("*/")
+            .process("/* abc *".into())
+            .unwrap_err();
+        assert_eq!(state.as_input().as_inner(), "/* abc *");
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Is("*/")),
+                Input::new_with_span("/* abc *", 8..8)
+            )
+        );
+
+        // Same as the general path
+        let input = "a */ b */";
+        let fast: (State<&str>, (Input<&str>, Input<&str>)) =
+            take_until_literal("*/").process(input.into()).unwrap();
+        let slow: (State<&str>, (Input<&str>, Input<&str>)) =
+            take_until(is("*/")).process(input.into()).unwrap();
+        assert_eq!(fast.1, slow.1);
+        assert_eq!(fast.0.as_input(), slow.0.as_input());
+    }
+
+    #[test]
+    fn can_take_until_literal_in_large_input() {
+        let input = format!("{}*/", "x".repeat(10_000));
+        let (state, (parsed, _)): (State<&str>, (Input<&str>, Input<&str>)) =
+            take_until_literal("*/")
+                .process(input.as_str().into())
+                .unwrap();
+        assert_eq!(parsed.as_inner().len(), 10_000);
+        assert_eq!(state.as_input().as_inner(), "");
+    }
+
+    #[test]
+    fn can_take_while() {
+        let (state, parsed): (State<&str>, Input<&str>) = take_while(|c| c.is_ascii_lowercase())
+            .process("abcDef".into())
+            .unwrap();
+        assert_eq!(parsed, "abc");
+        assert_eq!(state.as_input().as_inner(), "Def");
+        assert!(!state.is_err());
+
+        // Nothing matching is fine
+        let (state, parsed): (State<&str>, Input<&str>) = take_while(|c| c.is_ascii_lowercase())
+            .process("Def".into())
+            .unwrap();
+        assert_eq!(parsed, "");
+        assert_eq!(state.as_input().as_inner(), "Def");
+
+        let (state, parsed): (State<&str>, Input<&str>) =
+            take_till(|c| c == ',').process("abc".into()).unwrap();
+        assert_eq!(parsed, "abc");
+        assert_eq!(state.as_input().as_inner(), "");
+    }
+
+    #[test]
+    fn can_take_while1() {
+        let (state, parsed): (State<&str>, Input<&str>) = take_while1(|c| c.is_ascii_digit())
+            .process("123abc".into())
+            .unwrap();
+        assert_eq!(parsed, "123");
+        assert_eq!(state.as_input().as_inner(), "abc");
+
+        let state: State<&str> = take_while1(|c| c.is_ascii_digit())
+            .process("abc".into())
+            .unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Satisfy),
+                Input::new_with_span("abc", 0..1)
+            )
+        );
+    }
+
+    #[test]
+    fn can_take_while_m_n() {
+        let parser = take_while_m_n(2, 3, |c| c.is_ascii_digit());
+
+        let (state, parsed): (State<&str>, Input<&str>) = parser.process("12345".into()).unwrap();
+        assert_eq!(parsed, "123");
+        assert_eq!(state.as_input().as_inner(), "45");
+
+        let (state, parsed): (State<&str>, Input<&str>) = parser.process("12a".into()).unwrap();
+        assert_eq!(parsed, "12");
+        assert_eq!(state.as_input().as_inner(), "a");
+
+        let state: State<&str> = parser.process("1a".into()).unwrap_err();
+        assert_eq!(state.as_input().as_inner(), "1a");
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Satisfy),
+                Input::new_with_span("1a", 1..2)
+            )
+        );
+
+        let state: State<&str> = parser.process("1".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Satisfy),
+                Input::new_with_span("1", 1..1)
+            )
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn can_take_while_unicode() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            take_while(|c| c != '😍').process("héllô😍".into()).unwrap();
+        assert_eq!(parsed, "héllô");
+        assert_eq!(state.as_input().as_inner(), "😍");
+
+        let (state, parsed): (State<&str>, Input<&str>) =
+            take_while_m_n(1, 2, |c| c.is_alphabetic())
+                .process("éüa".into())
+                .unwrap();
+        assert_eq!(parsed, "éü");
+        assert_eq!(state.as_input().as_inner(), "a");
+    }
+
     #[cfg(feature = "unicode")]
     #[test]
     fn can_parse_unicode_take() {
@@ -0,0 +1,476 @@
+use super::whitespace::{is_whitespace, newline_len};
+use crate::parser::{
+    errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
+    input::{decode_glyph, Input, Underlying},
+    state::State,
+    Parser,
+};
+
+/// Parses a line comment, i.e. `start` followed by everything up to (but not including) the next
+/// newline, or the end of the input.
+///
+/// NOTE: Panics if `start` is empty.
+///
+/// ```
+/// # use errgonomic::combinators::line_comment;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// let (state, parsed): (State<&str>, Input<&str>) =
+///     line_comment("//").process("// a comment\nx".into()).unwrap();
+/// assert_eq!(parsed, "// a comment");
+/// assert_eq!(state.as_input().as_inner(), "\nx");
+/// ```
+pub fn line_comment<I: Underlying, E: CustomError, C>(start: I) -> impl Parser<I, Input<I>, E, C> {
+    let comment = Comment::Line(start).checked();
+    move |state| comment.process(state)
+}
+
+/// Parses a block comment, i.e. `open` followed by everything up to (and including) `close`. If
+/// `nested`, every `open` inside the comment needs its own `close`, e.g. `/* /* */ */`.
+///
+/// If the comment is never closed, a committed `Missing` error is returned over the whole comment
+/// (as it can't be anything else).
+///
+/// NOTE: Panics if `open` or `close` is empty.
+///
+/// ```
+/// # use errgonomic::combinators::block_comment;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// let (state, parsed): (State<&str>, Input<&str>) =
+///     block_comment("/*", "*/", true).process("/* a /* b */ c */ x".into()).unwrap();
+/// assert_eq!(parsed, "/* a /* b */ c */");
+/// assert_eq!(state.as_input().as_inner(), " x");
+///
+/// let state: State<&str> = block_comment("/*", "*/", true).process("/* a /* b */".into()).unwrap_err();
+/// assert!(state.errors().is_committed());
+/// assert_eq!(state.errors().to_string(), "missing `*/`");
+/// ```
+pub fn block_comment<I: Underlying, E: CustomError, C>(
+    open: I,
+    close: I,
+    nested: bool,
+) -> impl Parser<I, Input<I>, E, C> {
+    let comment = Comment::Block {
+        open,
+        close,
+        nested,
+    }
+    .checked();
+    move |state| comment.process(state)
+}
+
+/// Creates a trivia parser, which skips any amount of whitespace (by default) and comments, in any
+/// order. It never fails (unless a block comment isn't closed), and returns everything it skipped.
+///
+/// ```
+/// # use errgonomic::combinators::trivia;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// let trivia = trivia().line_comment("#").block_comment("(*", "*)", true);
+///
+/// let (state, parsed): (State<&str>, Input<&str>) =
+///     trivia.process(" # a\n(* b (* c *) *)\tx".into()).unwrap();
+/// assert_eq!(parsed, " # a\n(* b (* c *) *)\t");
+/// assert_eq!(state.as_input().as_inner(), "x");
+///
+/// let (state, parsed): (State<&str>, Input<&str>) = trivia.process("x".into()).unwrap();
+/// assert_eq!(parsed, "");
+/// ```
+pub fn trivia<I: Underlying, E: CustomError, C>() -> Trivia<I, E, C> {
+    Trivia {
+        whitespace: true,
+        comments: vec![],
+        _marker: std::marker::PhantomData,
+    }
+}
+
+/// A trivia parser, with the comments it skips. See `trivia`.
+#[derive(Debug, Clone)]
+pub struct Trivia<I: Underlying, E: CustomError, C = ()> {
+    /// Whether whitespace (including newlines) is skipped.
+    whitespace: bool,
+
+    /// The comments that are skipped.
+    comments: Vec<Comment<I>>,
+
+    _marker: std::marker::PhantomData<(E, C)>,
+}
+
+impl<I: Underlying, E: CustomError, C> Trivia<I, E, C> {
+    /// Sets whether whitespace (including newlines) is skipped, e.g. so that a grammar where
+    /// newlines matter can use `whitespace_not_newline` in a parser of its own instead.
+    pub fn whitespace(mut self, whitespace: bool) -> Self {
+        self.whitespace = whitespace;
+        self
+    }
+
+    /// Also skips line comments. See `line_comment`.
+    ///
+    /// NOTE: Panics if `start` is empty.
+    pub fn line_comment(mut self, start: I) -> Self {
+        self.comments.push(Comment::Line(start).checked());
+        self
+    }
+
+    /// Also skips block comments. See `block_comment`.
+    ///
+    /// NOTE: Panics if `open` or `close` is empty.
+    pub fn block_comment(mut self, open: I, close: I, nested: bool) -> Self {
+        self.comments.push(
+            Comment::Block {
+                open,
+                close,
+                nested,
+            }
+            .checked(),
+        );
+        self
+    }
+}
+
+impl<I: Underlying, E: CustomError, C> Parser<I, Input<I>, E, C> for Trivia<I, E, C> {
+    fn process(&self, state: State<I, E, C>) -> Result<I, Input<I>, E, C> {
+        let input = state.as_input().fork();
+        let bytes = input.as_bytes();
+        let mut len = 0;
+
+        'skip: loop {
+            match decode_glyph(&bytes[len..]) {
+                Some((c, n)) if self.whitespace && is_whitespace(c) => {
+                    len += n;
+                    continue;
+                }
+                _ => {}
+            }
+
+            for comment in &self.comments {
+                // NOTE: An empty comment can't be skipped, or this would never stop.
+                match comment.len(&bytes[len..]) {
+                    Some(Ok(n)) if n > 0 => {
+                        len += n;
+                        continue 'skip;
+                    }
+                    Some(Err(_)) => {
+                        let error = comment.unterminated(&input.skip(len));
+                        return Err(state.with_error(error));
+                    }
+                    _ => {}
+                }
+            }
+
+            break;
+        }
+
+        Ok((state.with_input(input.skip(len)), input.take(len)))
+    }
+}
+
+/// Parses `p`, skipping any trivia before and after it (see `trivia`). A generalization of
+/// `whitespace_wrapped`, e.g. to also skip comments.
+///
+/// NOTE: `trivia` should always succeed (like `Trivia` does), even if there is nothing to skip.
+///
+/// ```
+/// # use errgonomic::combinators::{padded_by, trivia, is};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// let parser = padded_by(is("x"), trivia().line_comment("//").block_comment("/*", "*/", false));
+///
+/// let (state, parsed): (State<&str>, Input<&str>) =
+///     parser.process("/* a */ x // b\n y".into()).unwrap();
+/// assert_eq!(parsed, "x");
+/// assert_eq!(state.as_input().as_inner(), "y");
+/// ```
+pub fn padded_by<I: Underlying, O, OT, E: CustomError, C, P, T>(
+    p: P,
+    trivia: T,
+) -> impl Parser<I, O, E, C>
+where
+    P: Parser<I, O, E, C>,
+    T: Parser<I, OT, E, C>,
+{
+    move |state: State<I, E, C>| {
+        let (state, _) = trivia.process(state)?;
+        let (state, o) = p.process(state)?;
+        let (state, _) = trivia.process(state)?;
+        Ok((state, o))
+    }
+}
+
+/// Parses `p`, then skips any trivia after it (see `trivia`), i.e. a token in a grammar where the
+/// trivia is only skipped at the very start, and then after every token.
+///
+/// NOTE: `trivia` should always succeed (like `Trivia` does), even if there is nothing to skip.
+///
+/// ```
+/// # use errgonomic::combinators::{lexeme, trivia, is};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::input::Input;
+/// # use errgonomic::parser::state::State;
+/// let parser = lexeme(is("x"), trivia().line_comment("#"));
+///
+/// let (state, parsed): (State<&str>, Input<&str>) = parser.process("x # b\n y".into()).unwrap();
+/// assert_eq!(parsed, "x");
+/// assert_eq!(state.as_input().as_inner(), "y");
+/// ```
+pub fn lexeme<I: Underlying, O, OT, E: CustomError, C, P, T>(
+    p: P,
+    trivia: T,
+) -> impl Parser<I, O, E, C>
+where
+    P: Parser<I, O, E, C>,
+    T: Parser<I, OT, E, C>,
+{
+    move |state: State<I, E, C>| {
+        let (state, o) = p.process(state)?;
+        let (state, _) = trivia.process(state)?;
+        Ok((state, o))
+    }
+}
+
+/// INTERNAL: A kind of comment.
+#[derive(Debug, Clone)]
+enum Comment<I: Underlying> {
+    /// A line comment, with its start. See `line_comment`.
+    Line(I),
+
+    /// A block comment. See `block_comment`.
+    Block { open: I, close: I, nested: bool },
+}
+
+impl<I: Underlying> Comment<I> {
+    /// Checks that the comment's delimiters aren't empty.
+    fn checked(self) -> Self {
+        let delimiters_empty = match &self {
+            Self::Line(start) => start.is_empty(),
+            Self::Block { open, close, .. } => open.is_empty() || close.is_empty(),
+        };
+        assert!(
+            !delimiters_empty,
+            "A comment's delimiters shouldn't be empty!"
+        );
+        self
+    }
+
+    /// Gets the length of the comment at the start of `bytes`, if there is one. If it's never
+    /// closed, returns the length of the rest of the input as an error.
+    fn len(&self, bytes: &[u8]) -> Option<core::result::Result<usize, usize>> {
+        match self {
+            Self::Line(start) => {
+                let start = literal_bytes(start);
+                if !bytes.starts_with(start) {
+                    return None;
+                }
+
+                let mut len = start.len();
+                while len < bytes.len() && newline_len(&bytes[len..]).is_none() {
+                    len += 1;
+                }
+
+                Some(Ok(len))
+            }
+            Self::Block {
+                open,
+                close,
+                nested,
+            } => {
+                let (open, close) = (literal_bytes(open), literal_bytes(close));
+                if !bytes.starts_with(open) {
+                    return None;
+                }
+
+                let mut len = open.len();
+                let mut depth = 1;
+
+                while depth > 0 {
+                    let rest = &bytes[len..];
+                    if rest.is_empty() {
+                        return Some(Err(bytes.len()));
+                    } else if rest.starts_with(close) {
+                        depth -= 1;
+                        len += close.len();
+                    } else if *nested && rest.starts_with(open) {
+                        depth += 1;
+                        len += open.len();
+                    } else {
+                        len += 1;
+                    }
+                }
+
+                Some(Ok(len))
+            }
+        }
+    }
+
+    /// Gets the (committed) error for the comment at the start of `input` never being closed.
+    fn unterminated<E: CustomError>(&self, input: &Input<I>) -> Error<I, E> {
+        let Self::Block { close, .. } = self else {
+            unreachable!("Line comments are always closed");
+        };
+
+        let missing = Error::new(
+            ErrorKind::expected(ExpectedError::Is(close.fork())),
+            input.skip_all(),
+        );
+        Error::new(ErrorKind::missing(missing), input.fork()).commit()
+    }
+
+    /// Parses the comment at the start of the input.
+    fn process<E: CustomError, C>(&self, state: State<I, E, C>) -> Result<I, Input<I>, E, C> {
+        let input = state.as_input().fork();
+        match self.len(input.as_bytes()) {
+            Some(Ok(len)) => Ok((state.with_input(input.skip(len)), input.take(len))),
+            Some(Err(_)) => Err(state.with_error(self.unterminated(&input))),
+            None => {
+                let (Self::Line(start) | Self::Block { open: start, .. }) = self;
+                Err(state.with_error(Error::new(
+                    ErrorKind::expected(ExpectedError::Is(start.fork())),
+                    input.take(start.len()),
+                )))
+            }
+        }
+    }
+}
+
+/// INTERNAL: Gets the bytes of a literal.
+fn literal_bytes<I: Underlying>(literal: &I) -> &[u8] {
+    literal
+        .byte_span(0, literal.len())
+        .expect("the literal to cover itself")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        combinators::{is, many},
+        parser::errors::DummyError,
+    };
+
+    #[test]
+    fn can_parse_line_comments() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            line_comment("--").process("-- a\r\nb".into()).unwrap();
+        assert_eq!(parsed, "-- a");
+        assert_eq!(state.as_input(), &"\r\nb");
+
+        let (state, parsed): (State<&str>, Input<&str>) =
+            line_comment("#").process("# é".into()).unwrap();
+        assert_eq!(parsed, "# é");
+        assert!(state.as_input().is_empty());
+
+        let state: State<&str> = line_comment("//").process("/ a".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::Is("//")),
+                Input::new_with_span("/ a", 0..2)
+            )
+        );
+    }
+
+    #[test]
+    fn can_parse_block_comments() {
+        let (state, parsed): (State<&str>, Input<&str>) = block_comment("/*", "*/", false)
+            .process("/* /* */ */".into())
+            .unwrap();
+        assert_eq!(parsed, "/* /* */");
+        assert_eq!(state.as_input(), &" */");
+
+        let (state, parsed): (State<&str>, Input<&str>) = block_comment("/*", "*/", true)
+            .process("/* /* */ /**/ */ */".into())
+            .unwrap();
+        assert_eq!(parsed, "/* /* */ /**/ */");
+        assert_eq!(state.as_input(), &" */");
+
+        let (_, parsed): (State<&str>, Input<&str>) = block_comment("{-", "-}", true)
+            .process("{-}-}".into())
+            .unwrap();
+        assert_eq!(parsed, "{-}-}");
+    }
+
+    #[test]
+    fn unterminated_block_comments_are_committed() {
+        let input = "a /* /* */";
+        let missing = Error::new(
+            ErrorKind::expected(ExpectedError::Is("*/")),
+            Input::new_with_span(input, 10..10),
+        );
+        let expected = Error::new(
+            ErrorKind::missing(missing),
+            Input::new_with_span(input, 2..10),
+        )
+        .commit();
+
+        let trivia = trivia().block_comment("/*", "*/", true);
+        let state: State<&str> = is("a").then(trivia).process(input.into()).unwrap_err();
+        assert_eq!(state.errors(), &expected);
+
+        let state: State<&str> = block_comment("/*", "*/", false)
+            .process("/* a".into())
+            .unwrap_err();
+        assert!(state.errors().is_committed());
+    }
+
+    #[test]
+    fn can_skip_trivia() {
+        let trivia = trivia().line_comment("//").block_comment("/*", "*/", true);
+
+        let (state, parsed): (State<&str>, Input<&str>) = trivia
+            .process(" // a\n\t/* b /* c */ */ /**///\nx".into())
+            .unwrap();
+        assert_eq!(parsed, " // a\n\t/* b /* c */ */ /**///\n");
+        assert_eq!(state.as_input(), &"x");
+
+        // Without whitespace, only the comments are skipped
+        let trivia = trivia.whitespace(false);
+        let (state, parsed): (State<&str>, Input<&str>) =
+            trivia.process("/* a *//* b */ x".into()).unwrap();
+        assert_eq!(parsed, "/* a *//* b */");
+        assert_eq!(state.as_input(), &" x");
+    }
+
+    #[test]
+    fn can_pad_with_trivia() {
+        let token = |s| padded_by(is(s), trivia().line_comment("#"));
+        let (state, parsed): (State<&str>, Vec<Input<&str>>) = many(token("a"))
+            .process(" a # 1\n a\n#2\na b".into())
+            .unwrap();
+        assert_eq!(parsed, ["a", "a", "a"]);
+        assert_eq!(state.as_input(), &"b");
+
+        // Only whitespace, like `whitespace_wrapped`
+        let (state, parsed): (State<&str>, Input<&str>) = padded_by(is("a"), trivia())
+            .process("\n a \t#".into())
+            .unwrap();
+        assert_eq!(parsed, "a");
+        assert_eq!(state.as_input(), &"#");
+
+        let (state, parsed): (State<&str>, Vec<Input<&str>>) =
+            many(lexeme(is("a"), trivia().block_comment("(*", "*)", false)))
+                .process("a(* 1 *) a a ".into())
+                .unwrap();
+        assert_eq!(parsed, ["a", "a", "a"]);
+        assert!(state.as_input().is_empty());
+
+        let state: State<&str> = lexeme(is("a"), trivia()).process(" a".into()).unwrap_err();
+        assert!(state.is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "A comment's delimiters shouldn't be empty!")]
+    fn empty_line_comment_panics() {
+        let _ = trivia::<&str, DummyError, ()>().line_comment("");
+    }
+
+    #[test]
+    #[should_panic(expected = "A comment's delimiters shouldn't be empty!")]
+    fn empty_block_comment_panics() {
+        let _ = block_comment::<&str, DummyError, ()>("", "*/", true);
+    }
+}
@@ -0,0 +1,275 @@
+//! Parsers for Unicode character classes, by general category. These are opt-in, and only
+//! available with the `unicode` feature enabled.
+//!
+//! See: https://www.unicode.org/reports/tr44/#General_Category_Values
+
+use super::glyph::{glyph, glyphs};
+use crate::parser::{
+    errors::{CustomError, ExpectedError, Result},
+    input::{Input, Underlying},
+    state::State,
+};
+use unicode_general_category::{get_general_category, GeneralCategory};
+
+/// Parses a single Unicode decimal digit, i.e. any character in the general category `Nd`. If
+/// there is no decimal digit, returns an error.
+///
+/// NOTE: Unlike `decimal_digit`, this includes non-ASCII digits (e.g. `٣` or `５`), so the output
+/// can't be handed straight to `str::parse`.
+///
+///```
+/// # use errgonomic::combinators::unicode_digit;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = unicode_digit.process("٣1".into()).unwrap();
+/// assert_eq!(parsed, "٣");
+/// assert_eq!(state.as_input().as_inner(), "1");
+///```
+pub fn unicode_digit<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyph(state, is_digit, ExpectedError::UnicodeDigit)
+}
+
+/// Parses a string of Unicode decimal digits (see `unicode_digit`) until it stops. If there is no
+/// decimal digit, returns an error.
+///
+///```
+/// # use errgonomic::combinators::unicode_digits;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = unicode_digits.process("١٢٣abc".into()).unwrap();
+/// assert_eq!(parsed, "١٢٣");
+/// assert_eq!(state.as_input().as_inner(), "abc");
+///```
+pub fn unicode_digits<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyphs(state, is_digit, ExpectedError::UnicodeDigit)
+}
+
+/// Parses a single Unicode letter, i.e. any character in the general categories `Lu`, `Ll`, `Lt`,
+/// `Lm` or `Lo`. If there is no letter, returns an error.
+///
+/// NOTE: This is stricter than `alphabetic_char`, which also includes other alphabetic
+/// characters, like combining marks (`Mn`/`Mc`) and letter-numbers (`Nl`).
+///
+///```
+/// # use errgonomic::combinators::unicode_letter;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = unicode_letter.process("Жx".into()).unwrap();
+/// assert_eq!(parsed, "Ж");
+/// assert_eq!(state.as_input().as_inner(), "x");
+///```
+pub fn unicode_letter<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyph(state, is_letter, ExpectedError::UnicodeLetter)
+}
+
+/// Parses a string of Unicode letters (see `unicode_letter`) until it stops. If there is no
+/// letter, returns an error.
+///
+///```
+/// # use errgonomic::combinators::unicode_letters;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = unicode_letters.process("héllo中 x".into()).unwrap();
+/// assert_eq!(parsed, "héllo中");
+/// assert_eq!(state.as_input().as_inner(), " x");
+///```
+pub fn unicode_letters<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyphs(state, is_letter, ExpectedError::UnicodeLetter)
+}
+
+/// Parses a single Unicode space separator, i.e. any character in the general category `Zs`. If
+/// there is no space separator, returns an error.
+///
+/// NOTE: This does *not* include tabs or newlines (those are control characters, `Cc`), nor line
+/// and paragraph separators (`Zl`/`Zp`). Use `whitespace` for those.
+///
+///```
+/// # use errgonomic::combinators::unicode_space;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = unicode_space.process("\u{3000}x".into()).unwrap();
+/// assert_eq!(parsed, "\u{3000}");
+/// assert_eq!(state.as_input().as_inner(), "x");
+///```
+pub fn unicode_space<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyph(state, is_space, ExpectedError::UnicodeSpace)
+}
+
+/// Parses a string of Unicode space separators (see `unicode_space`) until it stops. If there is
+/// no space separator, returns an error.
+///
+///```
+/// # use errgonomic::combinators::unicode_spaces;
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// # use errgonomic::parser::input::Input;
+/// let (state, parsed): (State<&str>, Input<&str>) = unicode_spaces.process(" \u{00A0}\tx".into()).unwrap();
+/// assert_eq!(parsed, " \u{00A0}");
+/// assert_eq!(state.as_input().as_inner(), "\tx");
+///```
+pub fn unicode_spaces<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyphs(state, is_space, ExpectedError::UnicodeSpace)
+}
+
+/// Whether `c` is in the general category `Nd`.
+#[inline]
+fn is_digit(c: char) -> bool {
+    get_general_category(c) == GeneralCategory::DecimalNumber
+}
+
+/// Whether `c` is in the general categories `L*`.
+#[inline]
+fn is_letter(c: char) -> bool {
+    matches!(
+        get_general_category(c),
+        GeneralCategory::UppercaseLetter
+            | GeneralCategory::LowercaseLetter
+            | GeneralCategory::TitlecaseLetter
+            | GeneralCategory::ModifierLetter
+            | GeneralCategory::OtherLetter
+    )
+}
+
+/// Whether `c` is in the general category `Zs`.
+#[inline]
+fn is_space(c: char) -> bool {
+    get_general_category(c) == GeneralCategory::SpaceSeparator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{
+        errors::{Error, ErrorKind},
+        Parser,
+    };
+
+    /// Every `char`, in order.
+    fn all_chars() -> impl Iterator<Item = char> {
+        '\0'..=char::MAX
+    }
+
+    #[test]
+    fn can_parse_unicode_digits() {
+        let (state, parsed): (State<&str>, Input<&str>) =
+            unicode_digits.process("0٣३５𝟗x".into()).unwrap();
+        assert_eq!(parsed, "0٣३５𝟗");
+        assert_eq!(state.as_input(), &"x");
+        assert!(!state.is_err());
+
+        // Superscripts (`No`) and roman numerals (`Nl`) aren't decimal digits
+        for s in ["²", "Ⅳ", "½"] {
+            let state: State<&str> = unicode_digit.process(s.into()).unwrap_err();
+            assert_eq!(
+                state.errors(),
+                &Error::new(
+                    ErrorKind::expected(ExpectedError::UnicodeDigit),
+                    Input::new_with_span(s, 0..s.len())
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn can_parse_unicode_letters() {
+        // One of each of `Lu`, `Ll`, `Lt`, `Lm` and `Lo`
+        let (state, parsed): (State<&str>, Input<&str>) =
+            unicode_letters.process("Aßǅʰª中́".into()).unwrap();
+        assert_eq!(parsed, "Aßǅʰª中");
+        // Combining marks are alphabetic, but not letters
+        assert_eq!(state.as_input(), &"\u{0301}");
+
+        let state: State<&str> = unicode_letter.process("1".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::UnicodeLetter),
+                Input::new_with_span("1", 0..1)
+            )
+        );
+    }
+
+    #[test]
+    fn can_parse_unicode_spaces() {
+        let (state, parsed): (State<&str>, Input<&str>) = unicode_spaces
+            .process(" \u{00A0}\u{1680}\u{2003}\u{202F}\u{205F}\u{3000}\t".into())
+            .unwrap();
+        assert_eq!(parsed, " \u{00A0}\u{1680}\u{2003}\u{202F}\u{205F}\u{3000}");
+        assert_eq!(state.as_input(), &"\t");
+
+        let state: State<&str> = unicode_space.process("\u{2028}".into()).unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::UnicodeSpace),
+                Input::new_with_span("\u{2028}", 0..3)
+            )
+        );
+    }
+
+    #[test]
+    fn digits_match_unicode_data() {
+        let digits = all_chars().filter(|c| is_digit(*c)).collect::<Vec<_>>();
+
+        // Per `UnicodeData.txt`, decimal digits always come in contiguous runs of `0` through `9`.
+        assert!(!digits.is_empty());
+        assert_eq!(digits.len() % 10, 0);
+        for run in digits.chunks(10) {
+            assert_eq!(run[9] as u32 - run[0] as u32, 9, "{:?}", run);
+            assert!(run.iter().all(|c| c.is_numeric()), "{:?}", run);
+        }
+
+        // ... and every digit should parse as exactly itself.
+        for c in digits {
+            let s = c.to_string();
+            let (state, parsed): (State<&str>, Input<&str>) =
+                unicode_digit.process(s.as_str().into()).unwrap();
+            assert_eq!(parsed, s.as_str());
+            assert!(state.as_input().is_empty());
+        }
+    }
+
+    #[test]
+    fn letters_match_unicode_data() {
+        for c in all_chars().filter(|c| is_letter(*c)) {
+            // Per `DerivedCoreProperties.txt`, all letters are `Alphabetic`.
+            assert!(c.is_alphabetic(), "{:?} should be alphabetic", c);
+        }
+
+        for c in ('a'..='z').chain('A'..='Z') {
+            assert!(is_letter(c));
+        }
+    }
+
+    #[test]
+    fn spaces_match_unicode_data() {
+        // Every `Zs` character in `UnicodeData.txt`.
+        let expected = [0x0020, 0x00A0, 0x1680]
+            .into_iter()
+            .chain(0x2000..=0x200A)
+            .chain([0x202F, 0x205F, 0x3000])
+            .map(|c| char::from_u32(c).unwrap())
+            .collect::<Vec<_>>();
+
+        let spaces = all_chars().filter(|c| is_space(*c)).collect::<Vec<_>>();
+        assert_eq!(spaces, expected);
+        assert!(spaces.iter().all(|c| c.is_whitespace()));
+    }
+}
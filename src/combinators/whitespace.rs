@@ -1,11 +1,15 @@
 use crate::parser::{
-    errors::{CustomError, Error, ErrorKind, ExpectedError, Result},
-    input::{Input, Underlying},
+    errors::{CustomError, ExpectedError, Result},
+    input::{decode_glyph, Input, Underlying},
     state::State,
     Parser,
 };
 
-use super::{between, maybe};
+use super::{
+    between,
+    glyph::{glyph, glyphs},
+    maybe,
+};
 
 /// Parses an input if it is whitespace (of any length), including newlines (or carriage returns).
 ///
@@ -21,61 +25,21 @@ use super::{between, maybe};
 /// # use errgonomic::parser::input::Input;
 /// # use errgonomic::parser::state::State;
 /// # use errgonomic::parser::errors::DummyError;
-/// let (state, parsed) = whitespace::<_, DummyError>.process("  \t\nabc".into()).unwrap();
+/// let (state, parsed) = whitespace::<_, DummyError, ()>.process("  \t\nabc".into()).unwrap();
 /// assert_eq!(parsed, "  \t\n");
 /// assert_eq!(state.as_input().as_inner(), "abc");
 /// ```
-pub fn whitespace<I: Underlying, E: CustomError>(mut state: State<I, E>) -> Result<I, Input<I>, E> {
-    #[cfg(not(feature = "unicode"))]
-    {
-        let mut len = 0;
-        let original_input = state.as_input().fork();
-        let input = state.as_input_mut();
-        while let Some(c) = input.peek() {
-            if !c.is_ascii_whitespace() {
-                break;
-            }
-
-            len += 1;
-            input.next();
-        }
-
-        if len == 0 {
-            return Err(state.with_error(Error::new(
-                ErrorKind::expected(ExpectedError::Whitespace),
-                original_input.take(1),
-            )));
-        }
-
-        Ok((state, original_input.take(len)))
-    }
-    #[cfg(feature = "unicode")]
-    {
-        let mut byte_len = 0;
-        let original_input = state.as_input().fork();
-        let input = state.as_input_mut();
-        while let Some(c) = input.peek_char() {
-            if !c.is_whitespace() {
-                break;
-            }
-
-            byte_len += c.len_utf8();
-            input.next_char();
-        }
-
-        if byte_len == 0 {
-            return Err(state.with_error(Error::new(
-                ErrorKind::expected(ExpectedError::Whitespace),
-                original_input.take(1),
-            )));
-        }
-
-        Ok((state, original_input.take(byte_len)))
-    }
+pub fn whitespace<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
+    glyphs(state, is_whitespace, ExpectedError::Whitespace)
 }
 
 /// Parses an input if it is whitespace (of any length), but *not* newlines (or carriage returns).
 ///
+/// NOTE: When `unicode` feature is enabled, this will parse all unicode whitespace (see
+/// `whitespace`), except for the newlines that `newlines` parses.
+///
 /// NOTE: Will error if the input is not whitespace.
 ///
 /// ```
@@ -84,41 +48,40 @@ pub fn whitespace<I: Underlying, E: CustomError>(mut state: State<I, E>) -> Resu
 /// # use errgonomic::parser::input::Input;
 /// # use errgonomic::parser::state::State;
 /// # use errgonomic::parser::errors::DummyError;
-/// let (state, parsed) = whitespace_not_newline::<_, DummyError>.process("  \t\nabc".into()).unwrap();
+/// let (state, parsed) = whitespace_not_newline::<_, DummyError, ()>.process("  \t\nabc".into()).unwrap();
 /// assert_eq!(parsed, "  \t");
 /// assert_eq!(state.as_input().as_inner(), "\nabc");
 /// ```
-pub fn whitespace_not_newline<I: Underlying, E: CustomError>(
-    mut state: State<I, E>,
-) -> Result<I, Input<I>, E> {
-    let mut len = 0;
+pub fn whitespace_not_newline<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
     let original_input = state.as_input().fork();
-    let input = state.as_input_mut();
+    let bytes = original_input.as_bytes();
+    let mut len = 0;
 
-    while let Some(c) = input.peek() {
-        if !c.is_ascii_whitespace()
-            || c == b'\n'
-            || (c == b'\r' && input.peek_nth(2) == Some(b'\n'))
-        {
+    while let Some((c, n)) = decode_glyph(&bytes[len..]) {
+        if !is_whitespace(c) || newline_len(&bytes[len..]).is_some() {
             break;
         }
 
-        len += 1;
-        input.next();
+        len += n;
     }
 
     if len == 0 {
-        return Err(state.with_error(Error::new(
-            ErrorKind::expected(ExpectedError::WhitespaceNoNewlines),
-            original_input.take(1),
-        )));
+        return glyph(state, |_| false, ExpectedError::WhitespaceNoNewlines);
     }
 
-    Ok((state, original_input.take(len)))
+    Ok((
+        state.with_input(original_input.skip(len)),
+        original_input.take(len),
+    ))
 }
 
 /// Parses an input if it a newline(s) (or carriage returns), but *not* whitespace.
 ///
+/// NOTE: When `unicode` feature is enabled, this will also parse the unicode newlines `NEL`
+/// (`U+0085`), `LINE SEPARATOR` (`U+2028`) and `PARAGRAPH SEPARATOR` (`U+2029`).
+///
 /// NOTE: Will error if the input is not whitespace.
 ///
 /// ```
@@ -127,32 +90,29 @@ pub fn whitespace_not_newline<I: Underlying, E: CustomError>(
 /// # use errgonomic::parser::input::Input;
 /// # use errgonomic::parser::state::State;
 /// # use errgonomic::parser::errors::DummyError;
-/// let (state, parsed) = newlines::<_, DummyError>.process("\n\r\n  \t\nabc".into()).unwrap();
+/// let (state, parsed) = newlines::<_, DummyError, ()>.process("\n\r\n  \t\nabc".into()).unwrap();
 /// assert_eq!(parsed, "\n\r\n");
 /// assert_eq!(state.as_input().as_inner(), "  \t\nabc");
 /// ```
-pub fn newlines<I: Underlying, E: CustomError>(mut state: State<I, E>) -> Result<I, Input<I>, E> {
-    let mut len = 0;
+pub fn newlines<I: Underlying, E: CustomError, C>(
+    state: State<I, E, C>,
+) -> Result<I, Input<I>, E, C> {
     let original_input = state.as_input().fork();
-    let input = state.as_input_mut();
-
-    while let Some(c) = input.peek() {
-        if !(c == b'\n' || (c == b'\r' && input.peek_nth(2) == Some(b'\n'))) {
-            break;
-        }
+    let bytes = original_input.as_bytes();
+    let mut len = 0;
 
-        len += 1;
-        input.next();
+    while let Some(n) = newline_len(&bytes[len..]) {
+        len += n;
     }
 
     if len == 0 {
-        return Err(state.with_error(Error::new(
-            ErrorKind::expected(ExpectedError::Newlines),
-            original_input.take(1),
-        )));
+        return glyph(state, |_| false, ExpectedError::Newlines);
     }
 
-    Ok((state, original_input.take(len)))
+    Ok((
+        state.with_input(original_input.skip(len)),
+        original_input.take(len),
+    ))
 }
 
 /// Parses an input wrapped in whitespace, on both ends.
@@ -168,9 +128,9 @@ pub fn newlines<I: Underlying, E: CustomError>(mut state: State<I, E>) -> Result
 /// assert_eq!(parsed, "abc");
 /// assert_eq!(state.as_input().as_inner(), "asdf");
 /// ```
-pub fn whitespace_wrapped<I: Underlying, E: CustomError, P: Parser<I, O, E>, O>(
+pub fn whitespace_wrapped<I: Underlying, E: CustomError, C, P: Parser<I, O, E, C>, O>(
     p: P,
-) -> impl Parser<I, O, E> {
+) -> impl Parser<I, O, E, C> {
     between(maybe(whitespace), p, maybe(whitespace))
 }
 
@@ -187,9 +147,15 @@ pub fn whitespace_wrapped<I: Underlying, E: CustomError, P: Parser<I, O, E>, O>(
 /// assert_eq!(parsed, "abc");
 /// assert_eq!(state.as_input().as_inner(), "\n    \r\nasdf");
 /// ```
-pub fn whitespace_not_newline_wrapped<I: Underlying, E: CustomError, P: Parser<I, O, E>, O>(
+pub fn whitespace_not_newline_wrapped<
+    I: Underlying,
+    E: CustomError,
+    C,
+    P: Parser<I, O, E, C>,
+    O,
+>(
     p: P,
-) -> impl Parser<I, O, E> {
+) -> impl Parser<I, O, E, C> {
     between(
         maybe(whitespace_not_newline),
         p,
@@ -197,6 +163,32 @@ pub fn whitespace_not_newline_wrapped<I: Underlying, E: CustomError, P: Parser<I
     )
 }
 
+/// Whether `c` is whitespace. See `whitespace`.
+#[inline]
+pub(crate) fn is_whitespace(c: char) -> bool {
+    #[cfg(not(feature = "unicode"))]
+    {
+        c.is_ascii_whitespace()
+    }
+    #[cfg(feature = "unicode")]
+    {
+        c.is_whitespace()
+    }
+}
+
+/// Gets the length (in bytes) of the newline at the start of `bytes`, if there is one. See
+/// `newlines` for what counts as a newline.
+#[inline]
+pub(crate) fn newline_len(bytes: &[u8]) -> Option<usize> {
+    match decode_glyph(bytes)? {
+        ('\n', n) => Some(n),
+        ('\r', _) if bytes.get(1) == Some(&b'\n') => Some(2),
+        #[cfg(feature = "unicode")]
+        ('\u{0085}' | '\u{2028}' | '\u{2029}', n) => Some(n),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -342,4 +334,31 @@ mod tests {
         assert!(!state.is_err());
         assert_eq!(state.as_input().as_inner(), "abc");
     }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn can_parse_unicode_newlines() {
+        let (state, parsed): (State<&str>, Input<&str>) = newlines
+            .process("\n\u{2028}\r\n\u{0085}\u{2029}\u{00A0}abc".into())
+            .unwrap();
+        assert_eq!(parsed, "\n\u{2028}\r\n\u{0085}\u{2029}");
+        assert_eq!(state.as_input().as_inner(), "\u{00A0}abc");
+
+        let (state, parsed): (State<&str>, Input<&str>) = whitespace_not_newline
+            .process("\u{00A0}\u{3000} \t\u{2028}abc".into())
+            .unwrap();
+        assert_eq!(parsed, "\u{00A0}\u{3000} \t");
+        assert_eq!(state.as_input().as_inner(), "\u{2028}abc");
+
+        let state: State<&str> = whitespace_not_newline
+            .process("\u{2029}".into())
+            .unwrap_err();
+        assert_eq!(
+            state.errors(),
+            &Error::new(
+                ErrorKind::expected(ExpectedError::WhitespaceNoNewlines),
+                Input::new_with_span("\u{2029}", 0..3)
+            )
+        );
+    }
 }
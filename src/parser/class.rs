@@ -0,0 +1,276 @@
+use core::{fmt, ops::RangeInclusive};
+
+/// A set of characters, which parses a single character in the set. Classes can be combined with
+/// `union`, `intersection`, `difference` and `negate`, and describe themselves in errors (as
+/// `ExpectedError::Class`).
+///
+/// NOTE: Without the `unicode` feature, each byte is matched as a `char` on its own, so only
+/// ASCII members are meaningful. With it, whole characters are matched.
+///
+/// ```
+/// # use errgonomic::combinators::{char_range, one_of};
+/// # use errgonomic::parser::Parser;
+/// # use errgonomic::parser::state::State;
+/// // Any ASCII letter but a vowel
+/// let consonant = char_range('a'..='z').difference(one_of("aeiou"));
+/// assert!(consonant.contains('b'));
+/// assert!(!consonant.contains('e'));
+///
+/// let state: State<&str> = consonant.process("e".into()).unwrap_err();
+/// assert_eq!(state.errors().to_string(), "expected a character in [b-df-hj-np-tv-z]");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CharClass {
+    /// The (sorted, non-overlapping and non-adjacent) ranges of characters in the class, or not in
+    /// the class if `negated` is set.
+    ranges: Vec<(char, char)>,
+
+    /// Whether the class is every character *except* those in `ranges`.
+    negated: bool,
+}
+
+impl CharClass {
+    /// Creates a class of all the characters in `ranges`. Empty ranges are ignored.
+    pub fn from_ranges<R: IntoIterator<Item = RangeInclusive<char>>>(ranges: R) -> Self {
+        let ranges = ranges
+            .into_iter()
+            .filter(|r| !r.is_empty())
+            .map(|r| (*r.start(), *r.end()))
+            .collect();
+
+        Self {
+            ranges: normalize(ranges),
+            negated: false,
+        }
+    }
+
+    /// Whether `c` is in the class.
+    pub fn contains(&self, c: char) -> bool {
+        let found = self
+            .ranges
+            .binary_search_by(|&(start, end)| {
+                if end < c {
+                    core::cmp::Ordering::Less
+                } else if start > c {
+                    core::cmp::Ordering::Greater
+                } else {
+                    core::cmp::Ordering::Equal
+                }
+            })
+            .is_ok();
+
+        found != self.negated
+    }
+
+    /// The class of every character *not* in this class.
+    pub fn negate(mut self) -> Self {
+        self.negated = !self.negated;
+        self
+    }
+
+    /// The class of every character in this class *or* `other`.
+    pub fn union(self, other: Self) -> Self {
+        match (self.negated, other.negated) {
+            (false, false) => Self::new(union(&self.ranges, &other.ranges), false),
+            // !a | !b = !(a & b)
+            (true, true) => Self::new(intersection(&self.ranges, &other.ranges), true),
+            // !a | b = !(a - b)
+            (true, false) => Self::new(difference(&self.ranges, &other.ranges), true),
+            (false, true) => Self::new(difference(&other.ranges, &self.ranges), true),
+        }
+    }
+
+    /// The class of every character in both this class *and* `other`.
+    pub fn intersection(self, other: Self) -> Self {
+        // a & b = !(!a | !b)
+        self.negate().union(other.negate()).negate()
+    }
+
+    /// The class of every character in this class, but *not* in `other`.
+    pub fn difference(self, other: Self) -> Self {
+        self.intersection(other.negate())
+    }
+
+    /// INTERNAL: Creates a class from already-normalized ranges.
+    fn new(ranges: Vec<(char, char)>, negated: bool) -> Self {
+        Self { ranges, negated }
+    }
+}
+
+impl fmt::Display for CharClass {
+    /// Writes the class in (regex-like) bracket syntax, e.g. `[a-fx]` or `[^"\\]`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        if self.negated {
+            write!(f, "^")?;
+        }
+
+        for &(start, end) in self.ranges.iter() {
+            write!(f, "{}", ClassChar(start))?;
+            if next_char(start) == Some(end) {
+                write!(f, "{}", ClassChar(end))?;
+            } else if start != end {
+                write!(f, "-{}", ClassChar(end))?;
+            }
+        }
+
+        write!(f, "]")
+    }
+}
+
+/// INTERNAL: A character in bracket syntax, escaped as needed.
+struct ClassChar(char);
+
+impl fmt::Display for ClassChar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            c @ ('\\' | '-' | '^' | '[' | ']') => write!(f, "\\{}", c),
+            c if c.is_control() => write!(f, "{}", c.escape_debug()),
+            c => write!(f, "{}", c),
+        }
+    }
+}
+
+/// INTERNAL: The character after `c`, skipping surrogates.
+fn next_char(c: char) -> Option<char> {
+    match c {
+        '\u{D7FF}' => Some('\u{E000}'),
+        c => char::from_u32(c as u32 + 1),
+    }
+}
+
+/// INTERNAL: The character before `c`, skipping surrogates.
+fn prev_char(c: char) -> Option<char> {
+    match c {
+        '\u{E000}' => Some('\u{D7FF}'),
+        c => (c as u32).checked_sub(1).and_then(char::from_u32),
+    }
+}
+
+/// INTERNAL: Sorts and merges overlapping or adjacent ranges.
+fn normalize(mut ranges: Vec<(char, char)>) -> Vec<(char, char)> {
+    ranges.sort_unstable();
+    let mut merged: Vec<(char, char)> = Vec::with_capacity(ranges.len());
+
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            // NOTE: If there's no next character, `last_end` is `char::MAX` and covers everything.
+            Some((_, last_end)) if next_char(*last_end).is_none_or(|n| start <= n) => {
+                *last_end = (*last_end).max(end);
+            }
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
+/// INTERNAL: The union of two sets of normalized ranges.
+fn union(a: &[(char, char)], b: &[(char, char)]) -> Vec<(char, char)> {
+    normalize(a.iter().chain(b).copied().collect())
+}
+
+/// INTERNAL: The intersection of two sets of normalized ranges.
+fn intersection(a: &[(char, char)], b: &[(char, char)]) -> Vec<(char, char)> {
+    let mut result = vec![];
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        let start = a[i].0.max(b[j].0);
+        let end = a[i].1.min(b[j].1);
+        if start <= end {
+            result.push((start, end));
+        }
+
+        // Move past whichever range ends first
+        if a[i].1 < b[j].1 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    result
+}
+
+/// INTERNAL: The characters in `a`, but not in `b`, for two sets of normalized ranges.
+fn difference(a: &[(char, char)], b: &[(char, char)]) -> Vec<(char, char)> {
+    let mut result = vec![];
+    let mut j = 0;
+
+    for &(start, end) in a {
+        let mut start = Some(start);
+
+        // Skip the ranges of `b` that end before this one starts
+        while j < b.len() && start.is_some_and(|s| b[j].1 < s) {
+            j += 1;
+        }
+
+        let mut k = j;
+        while let Some(s) = start {
+            if k >= b.len() || b[k].0 > end {
+                result.push((s, end));
+                break;
+            }
+
+            if b[k].0 > s {
+                result.push((s, prev_char(b[k].0).expect("to be after `s`")));
+            }
+
+            start = next_char(b[k].1).filter(|&n| n <= end);
+            k += 1;
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinators::{char_range, one_of};
+
+    #[test]
+    fn can_combine_classes() {
+        let hex = char_range('0'..='9')
+            .union(char_range('a'..='f'))
+            .union(char_range('A'..='F'));
+        assert_eq!(hex.to_string(), "[0-9A-Fa-f]");
+        assert!(hex.contains('B') && hex.contains('7') && !hex.contains('g'));
+
+        // Overlapping and adjacent ranges merge
+        assert_eq!(
+            CharClass::from_ranges(['a'..='c', 'b'..='e', 'f'..='f', 'h'..='h']).to_string(),
+            "[a-fh]"
+        );
+
+        let consonants = char_range('a'..='z').difference(one_of("aeiou"));
+        assert_eq!(consonants.to_string(), "[b-df-hj-np-tv-z]");
+
+        let not_hex = hex.clone().negate();
+        assert!(!not_hex.contains('a') && not_hex.contains('g'));
+
+        // De Morgan's laws
+        let a = char_range('a'..='m');
+        let b = char_range('h'..='z').union(one_of("0"));
+        let lhs = a.clone().union(b.clone()).negate();
+        let rhs = a.clone().negate().intersection(b.clone().negate());
+        let lhs2 = a.clone().intersection(b.clone()).negate();
+        let rhs2 = a.clone().negate().union(b.clone().negate());
+        for c in ('\0'..='\u{FF}').chain(['\u{D7FF}', '\u{E000}', char::MAX]) {
+            assert_eq!(lhs.contains(c), rhs.contains(c), "{:?}", c);
+            assert_eq!(lhs2.contains(c), rhs2.contains(c), "{:?}", c);
+            assert_eq!(
+                a.clone().difference(b.clone()).contains(c),
+                a.contains(c) && !b.contains(c),
+                "{:?}",
+                c
+            );
+        }
+
+        // Surrogates are skipped
+        let around_surrogates =
+            char_range('\u{D000}'..=char::MAX).difference(char_range('\u{E000}'..=char::MAX));
+        assert_eq!(around_surrogates, char_range('\u{D000}'..='\u{D7FF}'));
+    }
+}
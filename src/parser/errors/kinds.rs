@@ -1,5 +1,6 @@
 use super::*;
-use crate::parser::input::Underlying;
+use crate::parser::{class::CharClass, input::Underlying};
+use core::panic::Location;
 
 /// The kind of error we are dealing with.
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -26,6 +27,16 @@ where
     /// stage.
     Sequence(Vec<Error<I, E>>),
 
+    /// During `permutation`, something that should only be there once was found again.
+    Duplicate(I),
+
+    /// During `permutation`, something that should be there wasn't. Holds the error it gave.
+    Missing(Box<Error<I, E>>),
+
+    /// A repetition combinator (like `many`) repeated a parser that succeeded without consuming
+    /// anything, which would loop forever. In debug builds, holds where the combinator was created.
+    NoProgress(Option<&'static Location<'static>>),
+
     /// Custom error
     Custom(E),
 }
@@ -45,6 +56,21 @@ where
         Self::All(errors)
     }
 
+    /// Create a new `duplicate` error.
+    pub fn duplicate(found: I) -> Self {
+        Self::Duplicate(found)
+    }
+
+    /// Create a new `missing` error.
+    pub fn missing(error: Error<I, E>) -> Self {
+        Self::Missing(Box::new(error))
+    }
+
+    /// Create a new `no progress` error.
+    pub fn no_progress(location: Option<&'static Location<'static>>) -> Self {
+        Self::NoProgress(location)
+    }
+
     /// Create a new `custom` error.
     pub fn custom(err: E) -> Self {
         Self::Custom(err)
@@ -58,6 +84,9 @@ where
             Self::Expected(_) => 1,
             Self::All(errors) => errors.iter().map(|e| e.len()).sum(),
             Self::Sequence(errors) => errors.iter().map(|e| e.len()).sum(),
+            Self::Duplicate(_) => 1,
+            Self::Missing(_) => 1,
+            Self::NoProgress(_) => 1,
             Self::Custom(_) => 1,
         }
     }
@@ -72,6 +101,9 @@ pub enum ExpectedError<I: Underlying> {
     /// Expected something, but *not* this.
     Not(I),
 
+    /// We expected a specific thing/string to match, ignoring case, but didn't get it.
+    IsNoCase(I),
+
     /// Expected a digit with radix `n`
     /// NOTE: `n=10` and `n=16` specify that we want decimal or hexidecimal numbers, respectively.
     Digit(u32),
@@ -91,11 +123,71 @@ pub enum ExpectedError<I: Underlying> {
     /// Expected whitespace, not including newlines
     WhitespaceNoNewlines,
 
+    /// Expected a Unicode decimal digit (general category `Nd`).
+    #[cfg(feature = "unicode")]
+    UnicodeDigit,
+
+    /// Expected a Unicode letter (general categories `L*`).
+    #[cfg(feature = "unicode")]
+    UnicodeLetter,
+
+    /// Expected a Unicode space separator (general category `Zs`).
+    #[cfg(feature = "unicode")]
+    UnicodeSpace,
+
     /// Expected nothing/end-of-input, but found something.
     Nothing,
 
     /// Expected something, anything, but found nothing.
     Anything,
+
+    /// Expected an identifier.
+    Identifier,
+
+    /// Expected an identifier, but found this reserved keyword instead.
+    NotKeyword(I),
+
+    /// Expected this keyword, not followed by an identifier character.
+    Keyword(I),
+
+    /// Expected a character matching a predicate (see `satisfy`).
+    Satisfy,
+
+    /// Expected a character in this class (see `CharClass`).
+    /// NOTE: Boxed, as classes are much larger than any other expectation.
+    Class(Box<CharClass>),
+
+    /// Expected an element after this separator, which can't be trailing (see `SeparatedList`).
+    ElementAfter(I),
+
+    /// Expected the output to pass a check, described by this label (see `Parser::verify`).
+    Verified(&'static str),
+
+    /// Expected a number that fits in this type (see `integer`).
+    InRange(&'static str),
+
+    /// Expected a floating-point number (see `float`).
+    Float,
+
+    /// Expected a valid escape sequence in a string literal (see `string_literal`).
+    Escape,
+
+    /// Expected an indented block, i.e. a line indented further (see `indented_block`).
+    Indent,
+
+    /// Expected a line at the current indentation level (see `same_indent`).
+    SameIndent,
+
+    /// Expected a line at the current indentation level, but it's indented further.
+    UnexpectedIndent,
+
+    /// Expected a line to be unindented to the level of an enclosing block (see `indentation`).
+    Unindent,
+
+    /// Expected something other than the left-recursive rule itself, i.e. a seed for it to grow
+    /// from (see `left_recursive`). Only reported if the rule has nothing else to start with, as
+    /// otherwise, it's the other alternatives' errors that matter.
+    LeftRecursion,
 }
 
 impl<I, E> fmt::Display for ErrorKind<I, E>
@@ -104,6 +196,92 @@ where
     E: CustomError,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "TODO - Format: {:?}", self) // TODO: FORMAT!
+        match self {
+            Self::None => write!(f, "no error"),
+            Self::Committed(e) => write!(f, "{}", e),
+            Self::Expected(e) => write!(f, "{}", e),
+            Self::All(errors) => write_joined(f, errors, ", or "),
+            Self::Sequence(errors) => write_joined(f, errors, "\n"),
+            Self::Duplicate(s) => write!(f, "duplicate `{}`", text(s)),
+            Self::Missing(e) => match &e.kind {
+                Self::Expected(
+                    ExpectedError::Is(s) | ExpectedError::IsNoCase(s) | ExpectedError::Keyword(s),
+                ) => write!(f, "missing `{}`", text(s)),
+                _ => write!(f, "missing element ({})", e),
+            },
+            Self::NoProgress(location) => {
+                write!(
+                    f,
+                    "repeated parser made no progress, so it would loop forever"
+                )?;
+                match location {
+                    Some(location) => write!(f, " (at {})", location),
+                    None => Ok(()),
+                }
+            }
+            Self::Custom(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl<I: Underlying> fmt::Display for ExpectedError<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Is(s) => write!(f, "expected `{}`", text(s)),
+            Self::Not(s) => write!(f, "expected anything but `{}`", text(s)),
+            Self::IsNoCase(s) => write!(f, "expected `{}` (case-insensitive)", text(s)),
+            Self::Digit(10) => write!(f, "expected a decimal digit"),
+            Self::Digit(16) => write!(f, "expected a hexadecimal digit"),
+            Self::Digit(n) => write!(f, "expected a digit in base {}", n),
+            Self::Alpha => write!(f, "expected an alphabetic character"),
+            Self::AlphaNum => write!(f, "expected an alphanumeric character"),
+            Self::Whitespace => write!(f, "expected whitespace"),
+            Self::Newlines => write!(f, "expected a newline"),
+            Self::WhitespaceNoNewlines => write!(f, "expected whitespace (not including newlines)"),
+            #[cfg(feature = "unicode")]
+            Self::UnicodeDigit => write!(f, "expected a Unicode decimal digit (`Nd`)"),
+            #[cfg(feature = "unicode")]
+            Self::UnicodeLetter => write!(f, "expected a Unicode letter (`L*`)"),
+            #[cfg(feature = "unicode")]
+            Self::UnicodeSpace => write!(f, "expected a Unicode space separator (`Zs`)"),
+            Self::Nothing => write!(f, "expected end of input"),
+            Self::Anything => write!(f, "expected anything, but found end of input"),
+            Self::Identifier => write!(f, "expected an identifier"),
+            Self::NotKeyword(s) => write!(f, "`{}` is a reserved keyword", text(s)),
+            Self::Keyword(s) => write!(f, "expected keyword `{}`", text(s)),
+            Self::Satisfy => write!(f, "expected a matching character"),
+            Self::Class(class) => write!(f, "expected a character in {}", class),
+            Self::ElementAfter(s) => write!(f, "expected element after `{}`", text(s)),
+            Self::Verified(label) => write!(f, "expected {}", label),
+            Self::InRange(ty) => write!(f, "expected a number that fits in `{}`", ty),
+            Self::Float => write!(f, "expected a floating-point number"),
+            Self::Escape => write!(f, "expected a valid escape sequence"),
+            Self::Indent => write!(f, "expected an indented block"),
+            Self::SameIndent => write!(f, "expected a line at the same indentation"),
+            Self::UnexpectedIndent => write!(f, "unexpected indent"),
+            Self::Unindent => write!(f, "unindent does not match any outer indentation level"),
+            Self::LeftRecursion => write!(f, "expected a non-left-recursive alternative"),
+        }
     }
 }
+
+/// INTERNAL: Writes all the errors, separated by `sep`.
+fn write_joined<I, E>(f: &mut fmt::Formatter, errors: &[Error<I, E>], sep: &str) -> fmt::Result
+where
+    I: Underlying,
+    E: CustomError,
+{
+    for (i, e) in errors.iter().enumerate() {
+        if i > 0 {
+            write!(f, "{}", sep)?;
+        }
+        write!(f, "{}", e)?;
+    }
+
+    Ok(())
+}
+
+/// INTERNAL: Gets the (lossy) text of some underlying input, for error messages.
+fn text<I: Underlying>(i: &I) -> String {
+    String::from_utf8_lossy(i.byte_span(0, i.len()).unwrap_or_default()).into_owned()
+}
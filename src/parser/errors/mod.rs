@@ -14,7 +14,8 @@ pub use kinds::*;
 /// The result type for the parser.
 /// NOTE: This will always return a `State` since we may want to continue parsing even if an error
 /// has occurred. It is just that the `Ok` variant will contain the result of the parsing.
-pub type Result<I, O, E = DummyError> = core::result::Result<(State<I, E>, O), State<I, E>>;
+pub type Result<I, O, E = DummyError, C = ()> =
+    core::result::Result<(State<I, E, C>, O), State<I, E, C>>;
 
 /// Any possible errors that could have occurred during parsing.
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -71,6 +72,11 @@ where
         matches!(self.kind, ErrorKind::Committed(_))
     }
 
+    /// Get the kind of error.
+    pub fn kind(&self) -> &ErrorKind<I, E> {
+        &self.kind
+    }
+
     /// Get where the error is from.
     pub fn from(&self) -> Input<I> {
         self.from.fork()
@@ -86,6 +92,36 @@ where
         self.kind.len() == 0
     }
 
+    /// INTERNAL: Removes the errors that match `f` (e.g. placeholders that shouldn't be reported),
+    /// wherever they are. A list of errors that's left with only one is replaced by it.
+    pub(crate) fn without<F: Fn(&Self) -> bool>(self, f: &F) -> Self {
+        if f(&self) {
+            return Self::empty(self.from);
+        }
+
+        let (errors, all) = match self.kind {
+            ErrorKind::Committed(e) => {
+                let e = e.without(f);
+                return if e.is_empty() { e } else { e.commit() };
+            }
+            ErrorKind::All(errors) => (errors, true),
+            ErrorKind::Sequence(errors) => (errors, false),
+            _ => return self,
+        };
+
+        let mut errors = errors
+            .into_iter()
+            .map(|e| e.without(f))
+            .filter(|e| !e.is_empty())
+            .collect::<Vec<_>>();
+        match errors.len() {
+            0 => Self::empty(self.from),
+            1 => errors.remove(0),
+            _ if all => Self::new(ErrorKind::All(errors), self.from),
+            _ => Self::new(ErrorKind::Sequence(errors), self.from),
+        }
+    }
+
     /// Appends an error to the list of errors.
     pub fn push(&mut self, error: Error<I, E>) {
         match self.kind {
@@ -1,7 +1,9 @@
 mod span;
+mod spanned;
 mod underlying;
 
 pub use span::*;
+pub use spanned::*;
 pub use underlying::*;
 
 /// The input to the parser. Note that `Input` *never* actually deletes/shrinks the input, it only
@@ -49,6 +51,13 @@ impl<I: Underlying> Input<I> {
             .expect("the span to always cover a (sub)set of the underlying input")
     }
 
+    /// Gets the bytes covered by the span of the input.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.underlying
+            .byte_span(self.span.head(), self.span.tail())
+            .expect("the span to always cover a (sub)set of the underlying input")
+    }
+
     /// Checks if the input is empty.
     pub fn is_empty(&self) -> bool {
         self.span.is_empty() // TODO: What if the underlying is a reader and the reader is empty?
@@ -102,6 +111,12 @@ impl<I: Underlying> Input<I> {
         self.underlying.byte_at(self.span.head())
     }
 
+    /// Peeks at the next glyph of the input without consuming it, and returns it along with its
+    /// length in bytes. See `decode_glyph` for what a glyph is.
+    pub fn peek_glyph(&self) -> Option<(char, usize)> {
+        decode_glyph(self.as_bytes())
+    }
+
     /// Peeks at the next character (the one that would be returned by `next_char`) of the input
     /// without consuming it.
     /// WARN: Will skip over inself.d unicode!
@@ -218,6 +233,34 @@ impl<I: Underlying> Input<I> {
     }
 }
 
+/// Decodes the first glyph of `bytes`, and returns it along with its length in bytes. A glyph is a
+/// single `char` when the `unicode` feature is enabled, and a single byte (as a `char`) otherwise.
+///
+/// NOTE: When the `unicode` feature is enabled, invalid UTF-8 is treated as the end of the input.
+#[inline]
+pub(crate) fn decode_glyph(bytes: &[u8]) -> Option<(char, usize)> {
+    #[cfg(not(feature = "unicode"))]
+    {
+        bytes.first().map(|b| (*b as char, 1))
+    }
+    #[cfg(feature = "unicode")]
+    {
+        let len = match *bytes.first()? {
+            b @ 0x00..=0x7F => return Some((b as char, 1)),
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => return None,
+        };
+
+        let c = simdutf8::basic::from_utf8(bytes.get(..len)?)
+            .ok()?
+            .chars()
+            .next()?;
+        Some((c, len))
+    }
+}
+
 impl<I: Underlying> PartialEq for Input<I> {
     fn eq(&self, other: &Self) -> bool {
         self.underlying
@@ -329,6 +372,27 @@ mod tests {
         assert_eq!(input.peek_nth(5), None);
     }
 
+    #[test]
+    fn test_peek_glyph() {
+        let mut input = Input::new("hi");
+        assert_eq!(input.peek_glyph(), Some(('h', 1)));
+        input.next();
+        assert_eq!(input.peek_glyph(), Some(('i', 1)));
+        input.next();
+        assert_eq!(input.peek_glyph(), None);
+
+        // Without `unicode`, glyphs are just bytes
+        #[cfg(not(feature = "unicode"))]
+        assert_eq!(Input::new("é").peek_glyph(), Some(('\u{c3}', 1)));
+    }
+
+    #[test]
+    fn test_as_bytes() {
+        let input = Input::new_with_span("hello", Span::new(1, 4));
+        assert_eq!(input.as_bytes(), b"ell");
+        assert_eq!(input.skip(3).as_bytes(), b"");
+    }
+
     #[test]
     fn test_take() {
         let input = Input::new("hello");
@@ -613,6 +677,21 @@ mod tests {
         assert_eq!(input.next_char(), None);
     }
 
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn peeks_glyph_with_unicode() {
+        let input = Input::new("é😊");
+        assert_eq!(input.peek_glyph(), Some(('é', 2)));
+        assert_eq!(input.skip(2).peek_glyph(), Some(('😊', 4)));
+        assert_eq!(input.skip(6).peek_glyph(), None);
+
+        // Invalid (or truncated) UTF-8 is treated as the end of input
+        let bytes: &[u8] = &[0xFF, b'a'];
+        assert_eq!(Input::new(bytes).peek_glyph(), None);
+        let bytes: &[u8] = &[0xC3];
+        assert_eq!(Input::new(bytes).peek_glyph(), None);
+    }
+
     #[cfg(feature = "unicode")]
     #[test]
     fn peeks_nth_char() {
@@ -0,0 +1,130 @@
+use super::Span;
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+/// A value along with the span of the input it was parsed from. See `Parser::with_span`.
+///
+/// NOTE: `Deref`, `Debug` and `Display` all pass through to the value, so a `Spanned<T>` can
+/// mostly be used as if it were a `T`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Spanned<T> {
+    /// The parsed value.
+    pub value: T,
+
+    /// The span of the input the value was parsed from.
+    pub span: Span,
+}
+
+impl<T> Spanned<T> {
+    /// Creates a new `Spanned` value.
+    pub fn new(value: T, span: Span) -> Self {
+        Self { value, span }
+    }
+
+    /// Gets the value, throwing away the span.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Maps the value, keeping the span.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Spanned<U> {
+        Spanned::new(f(self.value), self.span)
+    }
+
+    /// Borrows the value, keeping the span.
+    pub fn as_ref(&self) -> Spanned<&T> {
+        Spanned::new(&self.value, self.span)
+    }
+
+    /// Gets the span covering both this and `other`, including anything between them. Useful for
+    /// the span of a node made of multiple children.
+    ///
+    /// ```
+    /// # use errgonomic::parser::input::{Span, Spanned};
+    /// let left = Spanned::new(1, Span::new(0, 1));
+    /// let right = Spanned::new(2, Span::new(4, 5));
+    /// assert_eq!(left.join(&right), Span::new(0, 5));
+    /// ```
+    pub fn join<U>(&self, other: &Spanned<U>) -> Span {
+        self.span.union_between(other.span)
+    }
+
+    /// Gets the span covering all of the given spanned values, including anything between them.
+    /// Returns `None` if there are none.
+    pub fn join_all<'a, L: IntoIterator<Item = &'a Spanned<T>>>(spanned: L) -> Option<Span>
+    where
+        T: 'a,
+    {
+        spanned
+            .into_iter()
+            .map(|s| s.span)
+            .reduce(|acc, span| acc.union_between(span))
+    }
+}
+
+impl<T> Deref for Spanned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Spanned<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Spanned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Spanned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        combinators::{decimal, is, many},
+        parser::{input::Input, state::State, Parser},
+    };
+
+    #[test]
+    fn can_parse_with_span() {
+        let (state, parsed): (State<&str>, Vec<Spanned<Input<&str>>>) =
+            many(decimal.with_span().then(is(",")).map(|(n, _)| n))
+                .process("1,23,456,".into())
+                .unwrap();
+        let spans = parsed.iter().map(|n| n.span).collect::<Vec<_>>();
+        assert_eq!(spans, [Span::new(0, 1), Span::new(2, 4), Span::new(5, 8)]);
+        assert_eq!(parsed[1].value, "23");
+        assert!(state.as_input().is_empty());
+
+        assert_eq!(Spanned::join_all(&parsed), Some(Span::new(0, 8)));
+        assert_eq!(parsed[0].join(&parsed[2]), Span::new(0, 8));
+        assert_eq!(Spanned::<u8>::join_all([]), None);
+    }
+
+    #[test]
+    fn spanned_passes_through() {
+        let mut spanned = Spanned::new(String::from("abc"), Span::new(3, 6));
+        assert_eq!(spanned.len(), 3);
+        spanned.push('d');
+        assert_eq!(spanned.to_string(), "abcd");
+        assert_eq!(format!("{:?}", spanned), "\"abcd\"");
+
+        let spanned = spanned.map(|s| s.len());
+        assert_eq!(spanned, Spanned::new(4, Span::new(3, 6)));
+        assert_eq!(*spanned.as_ref().value, 4);
+        assert_eq!(spanned.into_inner(), 4);
+    }
+}
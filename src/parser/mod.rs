@@ -1,14 +1,15 @@
 //! The parser types. This dictates how the parser is used and how it should be ran.
+pub mod class;
 pub mod errors;
 pub mod input;
 pub mod state;
 
-use errors::{CustomError, DummyError, Error, ErrorKind, Result};
-use input::Underlying;
+use errors::{CustomError, DummyError, Error, ErrorKind, ExpectedError, Result};
+use input::{Span, Spanned, Underlying};
 use state::State;
 
 /// The parser trait. Used to parse input.
-pub trait Parser<I, O, E = DummyError>
+pub trait Parser<I, O, E = DummyError, C = ()>
 where
     I: Underlying,
     E: CustomError,
@@ -25,7 +26,7 @@ where
     /// assert_eq!(parsed, "test");
     /// assert_eq!(state.as_input().as_inner(), "");
     /// ```
-    fn process(&self, state: State<I, E>) -> Result<I, O, E>;
+    fn process(&self, state: State<I, E, C>) -> Result<I, O, E, C>;
 
     /// Parses an input and returns an output.
     /// WARN: When making parsers, this should *not* be the function to process state and
@@ -37,7 +38,7 @@ where
     /// # use errgonomic::parser::state::State;
     /// # use errgonomic::parser::input::Input;
     /// # use errgonomic::parser::errors::DummyError;
-    /// let parsed = id::<_, DummyError>.parse("test").unwrap();
+    /// let parsed = id::<_, DummyError, ()>.parse("test").unwrap();
     /// assert_eq!(parsed, "test");
     /// ```
     #[inline]
@@ -59,15 +60,15 @@ where
     /// # use errgonomic::parser::Parser;
     /// # use errgonomic::parser::input::Input;
     /// # use errgonomic::parser::errors::DummyError;
-    /// let parsed = decimal::<_, DummyError>.map(|o: Input<&str>| o.as_inner().parse::<u32>().unwrap()).parse("123").unwrap();
+    /// let parsed = decimal::<_, DummyError, ()>.map(|o: Input<&str>| o.as_inner().parse::<u32>().unwrap()).parse("123").unwrap();
     /// assert_eq!(parsed, 123);
     /// ```
     #[inline]
-    fn map<O2, F: Fn(O) -> O2>(self, f: F) -> impl Parser<I, O2, E>
+    fn map<O2, F: Fn(O) -> O2>(self, f: F) -> impl Parser<I, O2, E, C>
     where
         Self: Sized,
     {
-        move |state: State<I, E>| {
+        move |state: State<I, E, C>| {
             self.process(state)
                 .map(|(state, output)| (state, f(output)))
         }
@@ -84,31 +85,31 @@ where
     /// # use errgonomic::parser::Parser;
     /// # use errgonomic::parser::input::Input;
     /// # use errgonomic::parser::errors::DummyError;
-    /// let parsed = decimal::<_, DummyError>.map(|o: Input<&str>| o.as_inner().parse::<u32>().unwrap()).parse("123").unwrap();
+    /// let parsed = decimal::<_, DummyError, ()>.map(|o: Input<&str>| o.as_inner().parse::<u32>().unwrap()).parse("123").unwrap();
     /// assert_eq!(parsed, 123);
     /// ```
     #[inline]
-    fn map_with_state<O2, F: Fn(State<I, E>, O) -> (State<I, E>, O2)>(
+    fn map_with_state<O2, F: Fn(State<I, E, C>, O) -> (State<I, E, C>, O2)>(
         self,
         f: F,
-    ) -> impl Parser<I, O2, E>
+    ) -> impl Parser<I, O2, E, C>
     where
         Self: Sized,
     {
-        move |state: State<I, E>| self.process(state).map(|(state, output)| f(state, output))
+        move |state: State<I, E, C>| self.process(state).map(|(state, output)| f(state, output))
     }
 
     /// Like `map_with_state`, but allows a parser to be formed from a function that takes in the
     /// output *and* the state. This is useful for dealing with lifetimes/moves/etc.
     #[inline]
-    fn map_with_res<O2, F: Fn(State<I, E>, O) -> Result<I, O2, E>>(
+    fn map_with_res<O2, F: Fn(State<I, E, C>, O) -> Result<I, O2, E, C>>(
         self,
         f: F,
-    ) -> impl Parser<I, O2, E>
+    ) -> impl Parser<I, O2, E, C>
     where
         Self: Sized,
     {
-        move |state: State<I, E>| -> Result<I, O2, E> {
+        move |state: State<I, E, C>| -> Result<I, O2, E, C> {
             let (state, output) = self.process(state)?;
             f(state, output)
         }
@@ -117,11 +118,11 @@ where
     /// Like `map`, but processes the output with a function that returns a (std) `Result`. If it's
     /// `Ok`, parsing continues as normal. If it's `Err`, the error is returned.
     #[inline]
-    fn map_res<O2, F: Fn(O) -> core::result::Result<O2, E>>(self, f: F) -> impl Parser<I, O2, E>
+    fn map_res<O2, F: Fn(O) -> core::result::Result<O2, E>>(self, f: F) -> impl Parser<I, O2, E, C>
     where
         Self: Sized,
     {
-        move |state: State<I, E>| {
+        move |state: State<I, E, C>| {
             let orig_input = state.as_input().fork();
             self.process(state).and_then(|(state, output)| {
                 f(output)
@@ -137,23 +138,104 @@ where
         }
     }
 
+    /// Pairs the output with the span of the input that the parser consumed.
+    /// ```
+    /// # use errgonomic::combinators::{decimal, whitespace_wrapped as ww};
+    /// # use errgonomic::parser::Parser;
+    /// # use errgonomic::parser::input::{Input, Span, Spanned};
+    /// # use errgonomic::parser::state::State;
+    /// let (_, parsed): (State<&str>, Spanned<Input<&str>>) =
+    ///     ww(decimal.with_span()).process("  123 ".into()).unwrap();
+    /// assert_eq!(parsed.value, "123");
+    /// assert_eq!(parsed.span, Span::new(2, 5));
+    /// ```
+    #[inline]
+    fn with_span(self) -> impl Parser<I, Spanned<O>, E, C>
+    where
+        Self: Sized,
+    {
+        self.map_with_span(Spanned::new)
+    }
+
+    /// Like `map`, but also passes the span of the input that the parser consumed to `f`.
+    /// ```
+    /// # use errgonomic::combinators::decimal;
+    /// # use errgonomic::parser::Parser;
+    /// # use errgonomic::parser::input::{Input, Span};
+    /// # use errgonomic::parser::errors::DummyError;
+    /// let parsed = decimal::<_, DummyError, ()>
+    ///     .map_with_span(|n: Input<&str>, span| (n.as_inner().len(), span))
+    ///     .parse("42")
+    ///     .unwrap();
+    /// assert_eq!(parsed, (2, Span::new(0, 2)));
+    /// ```
+    #[inline]
+    fn map_with_span<O2, F: Fn(O, Span) -> O2>(self, f: F) -> impl Parser<I, O2, E, C>
+    where
+        Self: Sized,
+    {
+        move |state: State<I, E, C>| {
+            let orig_input = state.as_input().fork();
+            let (state, output) = self.process(state)?;
+            let span = orig_input.subtract(state.as_input()).span();
+            Ok((state, f(output, span)))
+        }
+    }
+
+    /// Checks the output with a predicate. If it fails, a `Verified(label)` error is returned over
+    /// exactly what the parser matched, and no input is consumed. Unlike `map_res`, this doesn't
+    /// need a custom error, so `label` should describe what was expected.
+    /// ```
+    /// # use errgonomic::combinators::decimal;
+    /// # use errgonomic::parser::Parser;
+    /// # use errgonomic::parser::input::Input;
+    /// # use errgonomic::parser::state::State;
+    /// let byte = decimal.verify(|n: &Input<&str>| n.as_inner().parse::<u8>().is_ok(), "a number that fits in a byte");
+    ///
+    /// let (_, parsed): (State<&str>, Input<&str>) = byte.process("255".into()).unwrap();
+    /// assert_eq!(parsed, "255");
+    ///
+    /// let state: State<&str> = byte.process("256,".into()).unwrap_err();
+    /// assert_eq!(state.as_input().as_inner(), "256,");
+    /// assert_eq!(state.errors().from().as_inner(), "256");
+    /// assert_eq!(state.errors().to_string(), "expected a number that fits in a byte");
+    /// ```
+    #[inline]
+    fn verify<F: Fn(&O) -> bool>(self, f: F, label: &'static str) -> impl Parser<I, O, E, C>
+    where
+        Self: Sized,
+    {
+        move |state: State<I, E, C>| {
+            let (new_state, output) = self.process(state.fork())?;
+            if f(&output) {
+                return Ok((new_state, output));
+            }
+
+            let found = state.as_input().fork().subtract(new_state.as_input());
+            Err(state.with_error(Error::new(
+                ErrorKind::expected(ExpectedError::Verified(label)),
+                found,
+            )))
+        }
+    }
+
     /// Applies two parsers in sequence. Returns the output of both parsers.
     /// ```
     /// # use errgonomic::combinators::{decimal, hexadecimal};
     /// # use errgonomic::parser::Parser;
     /// # use errgonomic::parser::errors::DummyError;
-    /// let (first, second) = decimal::<_, DummyError>.then(hexadecimal).parse("123abc123").unwrap();
+    /// let (first, second) = decimal::<_, DummyError, ()>.then(hexadecimal).parse("123abc123").unwrap();
     /// assert_eq!(first, "123");
     /// assert_eq!(second, "abc123");
     /// ```
     #[inline]
-    fn then<O2, P2: Parser<I, O2, E>>(self, p2: P2) -> impl Parser<I, (O, O2), E>
+    fn then<O2, P2: Parser<I, O2, E, C>>(self, p2: P2) -> impl Parser<I, (O, O2), E, C>
     where
         Self: Sized,
     {
-        move |state: State<I, E>| -> Result<I, (O, O2), E> {
+        move |state: State<I, E, C>| -> Result<I, (O, O2), E, C> {
             self.process(state).and_then(
-                |(state, output1): (State<I, E>, _)| -> Result<I, (O, O2), E> {
+                |(state, output1): (State<I, E, C>, _)| -> Result<I, (O, O2), E, C> {
                     p2.process(state)
                         .map(|(state, output2)| (state, (output1, output2)))
                 },
@@ -168,7 +250,7 @@ where
     /// # use errgonomic::parser::Parser;
     /// # use errgonomic::parser::input::Input;
     /// # use errgonomic::parser::errors::DummyError;
-    /// let parsed = any((is::<_, DummyError>("dec:"), is("hex:")))
+    /// let parsed = any((is::<_, DummyError, ()>("dec:"), is("hex:")))
     ///                           .chain(|o: &Input<&str>| {
     ///                               if o.as_inner() == "dec:" {
     ///                                   decimal
@@ -182,11 +264,14 @@ where
     /// assert_eq!(parsed.1, "123");
     /// ```
     #[inline]
-    fn chain<O2, P2: Parser<I, O2, E>, F: Fn(&O) -> P2>(self, f: F) -> impl Parser<I, (O, O2), E>
+    fn chain<O2, P2: Parser<I, O2, E, C>, F: Fn(&O) -> P2>(
+        self,
+        f: F,
+    ) -> impl Parser<I, (O, O2), E, C>
     where
         Self: Sized,
     {
-        move |state: State<I, E>| {
+        move |state: State<I, E, C>| {
             self.process(state).and_then(|(state, output)| {
                 f(&output)
                     .process(state)
@@ -199,7 +284,7 @@ where
     ///
     /// NOTE: Replaces *all* the errors in the current state with the custom error.
     #[inline]
-    fn with_err(self, e: E) -> impl Parser<I, O, E>
+    fn with_err(self, e: E) -> impl Parser<I, O, E, C>
     where
         Self: Sized,
     {
@@ -221,27 +306,27 @@ where
     /// "said" to occur (make sure to get that right! See `with_err`'s source) and how state is
     /// managed (don't mutate state and then pass it, unless you ABSOLUTELY NEED TO).
     #[inline]
-    fn with_err_and<F>(self, f: F) -> impl Parser<I, O, E>
+    fn with_err_and<F>(self, f: F) -> impl Parser<I, O, E, C>
     where
         Self: Sized,
-        F: Fn(State<I, E>, State<I, E>) -> State<I, E>,
+        F: Fn(State<I, E, C>, State<I, E, C>) -> State<I, E, C>,
     {
-        move |state: State<I, E>| {
+        move |state: State<I, E, C>| {
             let original = state.fork();
             self.process(state)
-                .map_err(|after: State<I, E>| f(original, after))
+                .map_err(|after: State<I, E, C>| f(original, after))
         }
     }
 }
 
-impl<I, O, E, P> Parser<I, O, E> for P
+impl<I, O, E, C, P> Parser<I, O, E, C> for P
 where
     I: Underlying,
-    P: Fn(State<I, E>) -> Result<I, O, E>,
+    P: Fn(State<I, E, C>) -> Result<I, O, E, C>,
     E: CustomError,
 {
     #[inline]
-    fn process(&self, state: State<I, E>) -> Result<I, O, E> {
+    fn process(&self, state: State<I, E, C>) -> Result<I, O, E, C> {
         self(state)
     }
 }
@@ -1,11 +1,17 @@
+use core::{any::Any, fmt, panic::Location};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
 use super::{
-    errors::{CustomError, DummyError, Error},
+    errors::{CustomError, DummyError, Error, Result},
     input::{Input, Underlying},
 };
 
-/// The parser state.
-#[derive(Debug)]
-pub struct State<I, E = DummyError>
+/// The parser state. `C` is the user-defined context (see `State::with_context`), if the parser
+/// needs one.
+pub struct State<I, E = DummyError, C = ()>
 where
     I: Underlying,
     E: CustomError,
@@ -15,20 +21,194 @@ where
 
     /// Any errors that occurred during parsing.
     error: Error<I, E>,
+
+    /// Everything else: the indentation, the user-defined context and the memoized results.
+    /// NOTE: Behind one pointer, so that forking is cheap, and so that the state (which is also what
+    /// every parser fails with) stays small.
+    extras: Arc<Extras<I, E, C>>,
+}
+
+impl<I, E, C> fmt::Debug for State<I, E, C>
+where
+    I: Underlying,
+    E: CustomError,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("State")
+            .field("input", &self.input)
+            .field("error", &self.error)
+            .field("extras", &self.extras)
+            .finish()
+    }
+}
+
+/// The rarely used parts of the state. See `State::extras`.
+struct Extras<I, E, C>
+where
+    I: Underlying,
+    E: CustomError,
+{
+    /// The indentation state, if it's been used (see `indented_block`).
+    /// NOTE: Shared, so that memoized results can refer to it.
+    indentation: Option<Arc<Indentation>>,
+
+    /// The user-defined context, if there is any (see `State::with_context`).
+    /// NOTE: Shared, so that forking is cheap, and immutable, so that a fork (i.e. backtracking)
+    /// always sees the context from before it was forked.
+    context: Option<Arc<C>>,
+
+    /// The memoized results of the parse (see `memo`).
+    /// NOTE: Shared by every fork, as a result doesn't change when the parser backtracks.
+    memo: Arc<Mutex<Memo<I, E, C>>>,
+}
+
+impl<I, E, C> Default for Extras<I, E, C>
+where
+    I: Underlying,
+    E: CustomError,
+{
+    fn default() -> Self {
+        Self {
+            indentation: None,
+            context: None,
+            memo: Default::default(),
+        }
+    }
+}
+
+impl<I, E, C> Clone for Extras<I, E, C>
+where
+    I: Underlying,
+    E: CustomError,
+{
+    fn clone(&self) -> Self {
+        Self {
+            indentation: self.indentation.clone(),
+            context: self.context.clone(),
+            memo: self.memo.clone(),
+        }
+    }
+}
+
+impl<I, E, C> fmt::Debug for Extras<I, E, C>
+where
+    I: Underlying,
+    E: CustomError,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Extras")
+            .field("indentation", &self.indentation)
+            .field(
+                "context",
+                &self.context.as_ref().map(|_| core::any::type_name::<C>()),
+            )
+            .field("memo", &self.memo)
+            .finish()
+    }
+}
+
+/// The indentation state. See `State::indent`.
+#[derive(Debug, Clone)]
+struct Indentation {
+    /// The indentation levels of the enclosing indented blocks, innermost last.
+    indents: Vec<usize>,
+
+    /// How many columns a tab advances to (the next multiple of).
+    tab_width: usize,
 }
 
-impl<I, E> State<I, E>
+impl Default for Indentation {
+    fn default() -> Self {
+        Self {
+            indents: Vec::new(),
+            tab_width: 8,
+        }
+    }
+}
+
+/// The memoized results of a parse, by rule (i.e. where `memo` was called) and position.
+struct Memo<I, E, C>
+where
+    I: Underlying,
+    E: CustomError,
+{
+    entries: HashMap<(&'static Location<'static>, usize), Entry<I, E, C>>,
+    stats: MemoStats,
+
+    /// The entries, in the order they were memoized (so the newest can be forgotten).
+    log: Vec<(&'static Location<'static>, usize)>,
+}
+
+impl<I, E, C> Default for Memo<I, E, C>
+where
+    I: Underlying,
+    E: CustomError,
+{
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            stats: MemoStats::default(),
+            log: Vec::new(),
+        }
+    }
+}
+
+impl<I, E, C> fmt::Debug for Memo<I, E, C>
+where
+    I: Underlying,
+    E: CustomError,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Memo({} entries, {:?})", self.entries.len(), self.stats)
+    }
+}
+
+/// A memoized result: the state the rule left behind, and its output if it succeeded.
+struct Entry<I, E, C>
+where
+    I: Underlying,
+    E: CustomError,
+{
+    /// The state the rule was parsed from (other than the position), which it may depend on.
+    /// NOTE: The result is only reused from the same state, e.g. not after a change to the context
+    /// has been backtracked.
+    start_error: Error<I, E>,
+    start_indentation: Option<Arc<Indentation>>,
+    start_context: Option<Arc<C>>,
+
+    input: Input<I>,
+    error: Error<I, E>,
+    indentation: Option<Arc<Indentation>>,
+    context: Option<Arc<C>>,
+    output: Option<Box<dyn Any + Send>>,
+}
+
+/// How often memoized rules were looked up (see `State::memo_stats`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoStats {
+    /// How many times a rule's result was reused.
+    pub hits: usize,
+
+    /// How many times a rule had to be parsed.
+    pub misses: usize,
+}
+
+impl<I, E, C> State<I, E, C>
 where
     I: Underlying,
     E: CustomError,
 {
     /// Create a new `State` object.
+    ///
+    /// NOTE: This allocates the table of memoized results (see `memo`), as every fork has to
+    /// share it, even if nothing is memoized. So, create a state once per parse, not per parser.
     pub fn new(input: I) -> Self {
         let input = Input::new(input);
 
         Self {
             error: Error::empty(input.fork()),
             input,
+            extras: Default::default(),
         }
     }
 
@@ -64,11 +244,18 @@ where
         self
     }
 
+    /// INTERNAL: Changes the errors with `f`.
+    pub(crate) fn map_errors<F: FnOnce(Error<I, E>) -> Error<I, E>>(mut self, f: F) -> Self {
+        self.error = f(self.error);
+        self
+    }
+
     /// Fork the state.
     pub fn fork(&self) -> Self {
         Self {
             error: self.error.clone(),
             input: self.input.fork(),
+            extras: self.extras.clone(),
         }
     }
 
@@ -87,6 +274,217 @@ where
         self.input = input;
         self
     }
+
+    /// Gets the current indentation level, i.e. that of the innermost indented block (or 0, if
+    /// there is none).
+    pub fn indent(&self) -> usize {
+        self.indents().last().copied().unwrap_or(0)
+    }
+
+    /// Gets the indentation levels of all the enclosing indented blocks, innermost last.
+    pub fn indents(&self) -> &[usize] {
+        self.extras.indentation.as_ref().map_or(&[], |i| &i.indents)
+    }
+
+    /// Gets how many columns a tab advances to (the next multiple of), when measuring indentation.
+    pub fn tab_width(&self) -> usize {
+        self.extras.indentation.as_ref().map_or(8, |i| i.tab_width)
+    }
+
+    /// Sets how many columns a tab advances to (the next multiple of), when measuring indentation.
+    /// Defaults to 8.
+    ///
+    /// NOTE: Panics if `tab_width` is 0.
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        assert!(tab_width > 0, "The tab width must be at least 1!");
+        self.indentation_mut().tab_width = tab_width;
+        self
+    }
+
+    /// Enters an indented block at this indentation level.
+    pub(crate) fn push_indent(mut self, indent: usize) -> Self {
+        self.indentation_mut().indents.push(indent);
+        self
+    }
+
+    /// Leaves the innermost indented block.
+    pub(crate) fn pop_indent(mut self) -> Self {
+        self.indentation_mut().indents.pop();
+        self
+    }
+
+    /// INTERNAL: Gets the indentation state to modify, copying it if it's shared.
+    fn indentation_mut(&mut self) -> &mut Indentation {
+        Arc::make_mut(
+            self.extras_mut()
+                .indentation
+                .get_or_insert_with(Default::default),
+        )
+    }
+
+    /// Gets the user-defined context, if there is one (see `with_context`).
+    pub fn context(&self) -> Option<&C> {
+        self.extras.context.as_deref()
+    }
+
+    /// Sets the user-defined context, e.g. a symbol table, replacing any there was. Parsers can then
+    /// read and update it (see the `context` combinator), and it's restored whenever they
+    /// backtrack, as the state is forked.
+    ///
+    /// ```
+    /// # use errgonomic::parser::state::State;
+    /// # use errgonomic::parser::errors::DummyError;
+    /// let state: State<&str, DummyError, Vec<&str>> = State::new("x").with_context(vec!["int"]);
+    /// assert_eq!(state.context(), Some(&vec!["int"]));
+    ///
+    /// let state: State<&str, DummyError, Vec<&str>> = State::new("x");
+    /// assert_eq!(state.context(), None);
+    /// ```
+    ///
+    /// NOTE: The context's type is the state's (and so, every parser's) `C`, which is `()` unless
+    /// a parser needs a context. It can borrow from the input, e.g. to hold names.
+    pub fn with_context(self, context: C) -> Self {
+        self.with_shared_context(Arc::new(context))
+    }
+
+    /// Gets the user-defined context, shared, if there is one.
+    pub(crate) fn shared_context(&self) -> Option<Arc<C>> {
+        self.extras.context.clone()
+    }
+
+    /// Sets the user-defined context, shared.
+    pub(crate) fn with_shared_context(mut self, context: Arc<C>) -> Self {
+        self.extras_mut().context = Some(context);
+        self
+    }
+
+    /// Removes the user-defined context.
+    pub(crate) fn without_context(mut self) -> Self {
+        self.extras_mut().context = None;
+        self
+    }
+
+    /// Gets how often memoized rules were looked up so far in this parse (see `memo`).
+    ///
+    /// ```
+    /// # use errgonomic::combinators::{memo, is, any};
+    /// # use errgonomic::parser::Parser;
+    /// # use errgonomic::parser::state::{State, MemoStats};
+    /// let hello = || memo(is("hello").map(|_| ()));
+    /// let (state, _): (State<&str>, _) = any((hello().then(is("?")), hello().then(is("!"))))
+    ///     .process("hello!".into())
+    ///     .unwrap();
+    /// assert_eq!(state.memo_stats(), MemoStats { hits: 1, misses: 1 });
+    /// ```
+    pub fn memo_stats(&self) -> MemoStats {
+        self.memo().stats
+    }
+
+    /// Looks up the memoized result of `rule` at the current position, restoring the state it
+    /// left behind. It's only found if it was parsed from the same state as this one.
+    pub(crate) fn memo_lookup<O: Clone + 'static>(
+        &self,
+        rule: &'static Location<'static>,
+    ) -> Option<Result<I, O, E, C>> {
+        let mut memo = self.memo();
+        let entry = memo
+            .entries
+            .get(&(rule, self.input.span().head()))
+            .filter(|entry| self.is_memo_start(entry));
+        let Some(entry) = entry else {
+            memo.stats.misses += 1;
+            return None;
+        };
+
+        let state = Self {
+            input: entry.input.fork(),
+            error: entry.error.clone(),
+            extras: Arc::new(Extras {
+                indentation: entry.indentation.clone(),
+                context: entry.context.clone(),
+                memo: self.extras.memo.clone(),
+            }),
+        };
+        let output = entry.output.as_ref().map(|o| {
+            o.downcast_ref::<O>()
+                .expect("A memoized rule's output should always have the same type!")
+                .clone()
+        });
+        memo.stats.hits += 1;
+
+        Some(match output {
+            Some(output) => Ok((state, output)),
+            None => Err(state),
+        })
+    }
+
+    /// Memoizes the result of `rule`, parsed from `start`, i.e. the state it left behind and its
+    /// output.
+    pub(crate) fn memo_insert<O: Clone + Send + 'static>(
+        &self,
+        rule: &'static Location<'static>,
+        start: &Self,
+        output: Option<&O>,
+    ) {
+        let entry = Entry {
+            start_error: start.error.clone(),
+            start_indentation: start.extras.indentation.clone(),
+            start_context: start.extras.context.clone(),
+            input: self.input.fork(),
+            error: self.error.clone(),
+            indentation: self.extras.indentation.clone(),
+            context: self.extras.context.clone(),
+            output: output.map(|o| Box::new(o.clone()) as Box<dyn Any + Send>),
+        };
+        let key = (rule, start.input.span().head());
+        let mut memo = self.memo();
+        memo.entries.insert(key, entry);
+        memo.log.push(key);
+    }
+
+    /// INTERNAL: Checks if a memoized result was parsed from this state (other than the position).
+    /// NOTE: The context and indentation are compared by identity, as they're shared (and may not
+    /// be comparable). The errors are compared in full, so this costs as much as they're long.
+    fn is_memo_start(&self, entry: &Entry<I, E, C>) -> bool {
+        let same_indentation = match (&self.extras.indentation, &entry.start_indentation) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+        let same_context = match (&self.extras.context, &entry.start_context) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+        same_indentation && same_context && self.error == entry.start_error
+    }
+
+    /// Marks which results have been memoized so far (see `memo_forget`).
+    pub(crate) fn memo_mark(&self) -> usize {
+        self.memo().log.len()
+    }
+
+    /// Forgets the results memoized since `mark`, e.g. as they depend on a left-recursive rule's
+    /// seed, which has since grown.
+    pub(crate) fn memo_forget(&self, mark: usize) {
+        let mut memo = self.memo();
+        let forgotten = memo.log.split_off(mark);
+        for key in forgotten {
+            memo.entries.remove(&key);
+        }
+    }
+
+    /// INTERNAL: Locks the memoized results.
+    /// NOTE: They're never left half-updated, so they're still fine to use if a parser panicked.
+    fn memo(&self) -> MutexGuard<'_, Memo<I, E, C>> {
+        self.extras
+            .memo
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// INTERNAL: Gets the rest of the state to modify, copying it if it's shared.
+    fn extras_mut(&mut self) -> &mut Extras<I, E, C> {
+        Arc::make_mut(&mut self.extras)
+    }
 }
 
 impl<I> From<Input<I>> for State<I>
@@ -97,11 +495,12 @@ where
         Self {
             error: Error::empty(input.fork()),
             input,
+            extras: Default::default(),
         }
     }
 }
 
-impl<I, E> From<I> for State<I, E>
+impl<I, E, C> From<I> for State<I, E, C>
 where
     I: Underlying,
     E: CustomError,